@@ -0,0 +1,51 @@
+//! Allocations and time to build the type information of a class with 200 methods and 200
+//! fields that each have a getter and a setter
+//!
+//! Run with `cargo bench --bench class_builder --features mlua,lua54,vendored`
+
+mod common;
+
+use std::time::Instant;
+
+use mlua_extras::{
+    mlua::UserData,
+    typed::{TypedClassBuilder, TypedDataFields, TypedDataMethods, TypedUserData},
+};
+
+const MEMBERS: usize = 200;
+const ITERATIONS: usize = 100;
+
+struct Large;
+
+impl UserData for Large {}
+
+impl TypedUserData for Large {
+    fn add_fields<'lua, F: TypedDataFields<'lua, Self>>(fields: &mut F) {
+        for i in 0..MEMBERS {
+            let name = format!("field{i}");
+            fields.add_field_method_get(&name, move |_, _| Ok(Some(i)));
+            fields.add_field_method_set(&name, |_, _, _: Option<String>| Ok(()));
+        }
+    }
+
+    fn add_methods<'lua, M: TypedDataMethods<'lua, Self>>(methods: &mut M) {
+        for i in 0..MEMBERS {
+            methods.add_method(&format!("method{i}"), move |_, _, (_a, _b): (String, Option<f64>)| Ok(i));
+        }
+    }
+}
+
+fn main() {
+    let start = Instant::now();
+    let before = common::allocations();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(TypedClassBuilder::new::<Large>());
+    }
+    let allocations = common::allocations() - before;
+
+    println!(
+        "class_builder: {} allocations and {:?} per class with {MEMBERS} methods and {MEMBERS} fields",
+        allocations / ITERATIONS,
+        start.elapsed() / ITERATIONS as u32,
+    );
+}
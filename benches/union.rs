@@ -0,0 +1,48 @@
+//! Allocations and time to merge 200 types into a single union by cloning the accumulated union
+//! with `|` compared to merging in place with `Type::union_with`
+//!
+//! Run with `cargo bench --bench union --features mlua,lua54,vendored`
+
+mod common;
+
+use std::time::Instant;
+
+use mlua_extras::typed::Type;
+
+const MEMBERS: usize = 200;
+const ITERATIONS: usize = 100;
+
+fn members() -> Vec<Type> {
+    (0..MEMBERS)
+        .map(|i| Type::array(Type::single(format!("Member{i}"))))
+        .collect()
+}
+
+fn measure(name: &str, merge: impl Fn(&mut Type, Type)) {
+    let mut elapsed = std::time::Duration::ZERO;
+    let mut allocations = 0;
+    for _ in 0..ITERATIONS {
+        let members = members();
+
+        let start = Instant::now();
+        let before = common::allocations();
+        let mut ty = Type::single("nil");
+        for member in members {
+            merge(&mut ty, member);
+        }
+        std::hint::black_box(ty);
+        allocations += common::allocations() - before;
+        elapsed += start.elapsed();
+    }
+
+    println!(
+        "{name}: {} allocations and {:?} per union of {MEMBERS} types",
+        allocations / ITERATIONS,
+        elapsed / ITERATIONS as u32,
+    );
+}
+
+fn main() {
+    measure("clone and `|`", |ty, member| *ty = ty.clone() | member);
+    measure("union_with", |ty, member| ty.union_with(member));
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use mlua_extras::{
-    mlua::{self, FromLua, Lua, LuaSerdeExt, MetaMethod, UserDataMethods, Value, Variadic},
+    mlua::{self, FromLua, Lua, LuaSerdeExt, MetaMethod, Value, Variadic},
     typed::{
         generator::{Definition, Definitions, DefinitionFileGenerator},
         TypedDataFields, TypedDataMethods, TypedUserData,
@@ -161,9 +161,9 @@ fn main() -> mlua::Result<()> {
             .register_enum::<SystemColor>()?
             .register_enum::<Color>()?
             .register_class::<Example>()
-            .value::<Example, _>("example")
-            .function::<String, (), _>("greet", ())
-            .function::<Color, (), _>("printColor", ())
+            .value::<Example>("example")
+            .function::<String, ()>("greet", ())
+            .function::<Color, ()>("printColor", ())
         )
         .finish();
 
@@ -4,6 +4,7 @@ extern crate quote;
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use proc_macro_error::{proc_macro_error, abort};
+use quote::ToTokens;
 use syn::spanned::Spanned;
 use venial::{parse_item, Fields, Item};
 
@@ -25,12 +26,12 @@ pub fn derive_user_data(input: TokenStream) -> TokenStream {
     quote!(
         impl mlua_extras::mlua::UserData for #name {
             fn add_fields<'lua, F: mlua_extras::mlua::UserDataFields<'lua, Self>>(fields: &mut F) {
-                let mut wrapper = mlua_extras::typed::WrappedGenerator::new(fields);
+                let mut wrapper = mlua_extras::typed::WrappedBuilder::new(fields);
                 <#name as mlua_extras::typed::TypedUserData>::add_fields(&mut wrapper);
             }
 
             fn add_methods<'lua, M: mlua_extras::mlua::UserDataMethods<'lua, Self>>(methods: &mut M) {
-                let mut wrapper = mlua_extras::typed::WrappedGenerator::new(methods);
+                let mut wrapper = mlua_extras::typed::WrappedBuilder::new(methods);
                 <#name as mlua_extras::typed::TypedUserData>::add_methods(&mut wrapper);
             }
         }
@@ -44,6 +45,27 @@ pub fn derive_typed(input: TokenStream) -> TokenStream {
     match parse_item(input.clone()) {
         Ok(Item::Struct(struct_type)) => {
             let name = struct_type.name.clone();
+
+            // `#[typed(transparent)]` uses the type of the single wrapped field, i.e. `struct Meters(f64)` is a `number`
+            if has_typed_flag(&struct_type.attributes, "transparent") {
+                let fields = match &struct_type.fields {
+                    Fields::Tuple(tf) => tf.fields.iter().map(|(field, _)| field.ty.clone()).collect::<Vec<_>>(),
+                    Fields::Named(named) => named.fields.iter().map(|(field, _)| field.ty.clone()).collect::<Vec<_>>(),
+                    Fields::Unit => Vec::new(),
+                };
+                if fields.len() != 1 {
+                    abort!(input.span(), "`#[typed(transparent)]` requires a struct with exactly one field")
+                }
+                let ty = &fields[0];
+                return quote!(
+                    impl mlua_extras::typed::Typed for #name {
+                        fn ty() -> mlua_extras::typed::Type {
+                            <#ty as mlua_extras::typed::Typed>::ty()
+                        }
+                    }
+                ).into();
+            }
+
             let value = syn::LitStr::new(name.to_string().as_str(), Span::call_site());
             quote!(
                 impl mlua_extras::typed::Typed for #name {
@@ -54,12 +76,20 @@ pub fn derive_typed(input: TokenStream) -> TokenStream {
             )
         },
         Ok(Item::Enum(enum_type)) => {
+            // `#[typed(tag = "type")]` and `#[typed(tag = "t", content = "c")]` match serde's
+            // internally and adjacently tagged enum representations
+            let tag = typed_value(&enum_type.attributes, "tag");
+            let content = typed_value(&enum_type.attributes, "content");
+            if content.is_some() && tag.is_none() {
+                abort!(input.span(), "`#[typed(content = \"...\")]` requires `#[typed(tag = \"...\")]`")
+            }
+
             let variants = enum_type.variants
                 .iter()
                 .map(|(variant, _punc)| {
                     let name = format!("\"{}\"", variant.name);
-                    match &variant.fields {
-                        Fields::Unit => quote!{ mlua_extras::typed::Type::single(#name) },
+                    let data = match &variant.fields {
+                        Fields::Unit => None,
                         Fields::Tuple(tf) => {
                             let tuple_values = tf.fields.iter().map(|(field, _)| {
                                 let ty = field.ty.clone();
@@ -68,25 +98,34 @@ pub fn derive_typed(input: TokenStream) -> TokenStream {
 
                             if tuple_values.len() == 1 {
                                 let first = tuple_values.first().unwrap();
-                                quote!{ #first }
+                                Some(quote!{ #first })
                             } else {
-                                quote!{ mlua_extras::typed::Type::Tuple(Vec::from([
+                                Some(quote!{ mlua_extras::typed::Type::Tuple(Vec::from([
                                         #(#tuple_values,)*
-                                ])) }
+                                ])) })
                             }
                         },
-                        Fields::Named(named) => {
-                            let tuple_values = named.fields.iter().map(|(field, _)| {
-                                let name = field.name.to_string();
-                                let ty = field.ty.clone();
-                                quote!{ (#name, <#ty as mlua_extras::typed::Typed>::ty()) }
-                            }).collect::<Vec<_>>();
-                            quote!{ mlua_extras::typed::Type::Struct(std::collections::HashMap::from([
-                                    #(#tuple_values,)*
+                        Fields::Named(_) => Some(named_fields(&variant.fields, None)),
+                    };
+
+                    match (&tag, &content, data) {
+                        (None, _, None) => quote!{ mlua_extras::typed::Type::single(#name) },
+                        (None, _, Some(data)) => data,
+                        (Some(tag), None, _) => match &variant.fields {
+                            Fields::Tuple(_) => abort!(
+                                variant.name.span(),
+                                "tuple variants can't be internally tagged, add `#[typed(content = \"...\")]` to adjacently tag them"
+                            ),
+                            fields => named_fields(fields, Some((tag, &name))),
+                        },
+                        (Some(tag), Some(content), data) => {
+                            let data = data.map(|data| quote!{ (#content, #data), });
+                            quote!{ mlua_extras::typed::Type::Struct(std::collections::BTreeMap::from([
+                                (#tag, mlua_extras::typed::Type::single(#name)),
+                                #data
                             ])) }
-                        }
+                        },
                     }
-                    
                 })
                 .collect::<Vec<_>>();
 
@@ -108,3 +147,122 @@ pub fn derive_typed(input: TokenStream) -> TokenStream {
         _ => abort!(input.span(), "only `struct` and `enum` types are supported for Typed")
     }.into()
 }
+
+/// Collect the rustdoc (`///`) comments of an item or field into a single doc string
+fn doc_comment(attributes: &[venial::Attribute]) -> Option<String> {
+    let lines = attributes
+        .iter()
+        .filter(|attr| attr.get_single_path_segment().is_some_and(|v| v == "doc"))
+        .filter_map(|attr| {
+            let tokens = attr.get_value_tokens().iter().cloned().collect::<TokenStream2>();
+            syn::parse2::<syn::LitStr>(tokens).ok()
+        })
+        .map(|v| {
+            let line = v.value();
+            line.strip_prefix(' ').map(|v| v.to_string()).unwrap_or(line)
+        })
+        .collect::<Vec<_>>();
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Struct type of named fields, with a `tag` field set to the variant name when one is given
+fn named_fields(fields: &Fields, tag: Option<(&String, &String)>) -> TokenStream2 {
+    let mut values = tag
+        .map(|(tag, name)| quote!{ (#tag, mlua_extras::typed::Type::single(#name)) })
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    if let Fields::Named(named) = fields {
+        values.extend(named.fields.iter().map(|(field, _)| {
+            let name = field.name.to_string();
+            let ty = field.ty.clone();
+            quote!{ (#name, <#ty as mlua_extras::typed::Typed>::ty()) }
+        }));
+    }
+
+    quote!{ mlua_extras::typed::Type::Struct(std::collections::BTreeMap::from([
+        #(#values,)*
+    ])) }
+}
+
+/// Value of a `#[typed(<key> = "value")]` attribute
+fn typed_value(attributes: &[venial::Attribute], key: &str) -> Option<String> {
+    attributes
+        .iter()
+        .filter(|attr| attr.get_single_path_segment().is_some_and(|v| v == "typed"))
+        .find_map(|attr| {
+            let tokens = attr.get_value_tokens();
+            tokens.windows(3).find_map(|window| match window {
+                [proc_macro2::TokenTree::Ident(ident), proc_macro2::TokenTree::Punct(eq), proc_macro2::TokenTree::Literal(value)]
+                    if ident == key && eq.as_char() == '=' =>
+                {
+                    syn::parse2::<syn::LitStr>(value.clone().into_token_stream()).ok().map(|v| v.value())
+                }
+                _ => None,
+            })
+        })
+}
+
+/// Whether the attributes contain `#[typed(<flag>)]`
+fn has_typed_flag(attributes: &[venial::Attribute], flag: &str) -> bool {
+    attributes.iter().any(|attr| {
+        attr.get_single_path_segment().is_some_and(|v| v == "typed")
+            && attr.get_value_tokens().iter().any(|t| matches!(t, proc_macro2::TokenTree::Ident(v) if v == flag))
+    })
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(TypedModule, attributes(typed))]
+pub fn derive_typed_module(input: TokenStream) -> TokenStream {
+    let input = TokenStream2::from(input);
+    let struct_type = match parse_item(input.clone()) {
+        Ok(Item::Struct(struct_type)) => struct_type,
+        Err(err) => abort!(err.span(), "{}", err),
+        _ => abort!(input.span(), "only `struct` types with named fields are supported for TypedModule")
+    };
+
+    let Fields::Named(named) = &struct_type.fields else {
+        abort!(input.span(), "only `struct` types with named fields are supported for TypedModule")
+    };
+
+    let name = struct_type.name.clone();
+    let documentation = match doc_comment(&struct_type.attributes) {
+        Some(doc) => quote!{ Some(#doc.to_string()) },
+        None => quote!{ None },
+    };
+
+    let fields = named.fields.iter().map(|(field, _)| {
+        let ident = field.name.clone();
+        let key = ident.to_string();
+        let ty = field.ty.clone();
+        let doc = doc_comment(&field.attributes).map(|doc| quote!{ fields.document(#doc); });
+
+        if has_typed_flag(&field.attributes, "module") {
+            quote!{
+                #doc
+                let _ = value.#ident;
+                fields.add_module::<#ty>(#key)?;
+            }
+        } else {
+            quote!{
+                #doc
+                fields.add_field(#key, value.#ident)?;
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    quote!(
+        impl mlua_extras::typed::TypedModule for #name {
+            fn documentation() -> Option<String> {
+                #documentation
+            }
+
+            fn add_fields<'lua, F: mlua_extras::typed::TypedModuleFields<'lua>>(fields: &mut F) -> mlua_extras::mlua::Result<()> {
+                let value = <#name as Default>::default();
+                #(#fields)*
+                Ok(())
+            }
+        }
+    ).into()
+}
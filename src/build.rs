@@ -0,0 +1,74 @@
+//! Helpers for generating definition files from a cargo build script.
+//!
+//! Building [`Definitions`] only collects type information so no [`mlua::Lua`] instance is
+//! needed, which makes it safe to run from `build.rs`.
+//!
+//! ```no_run
+//! use mlua_extras::typed::generator::{Definition, Definitions};
+//!
+//! let definitions = Definitions::start()
+//!     .define("init", Definition::start().value::<String>("name"))
+//!     .finish();
+//!
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! mlua_extras::build::emit(definitions, out_dir).unwrap();
+//!
+//! // Rerun when the source the definitions are built from changes, not the written files
+//! println!("cargo:rerun-if-changed=src/api.rs");
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::typed::generator::{DefinitionFileGenerator, Definitions, GenError, SkippedEntry};
+
+/// Write each definition group into `out_dir` returning the paths of the written files
+pub fn emit<P: AsRef<Path>>(definitions: Definitions<'_>, out_dir: P) -> Result<Vec<PathBuf>, GenError> {
+    emit_with(&DefinitionFileGenerator::new(definitions), out_dir)
+}
+
+/// Same as [`emit`] but with a configured [`DefinitionFileGenerator`], i.e. a custom extension or header
+///
+/// Entries skipped by a non [`strict`][DefinitionFileGenerator::strict] generator are dropped,
+/// use [`emit_with_collect`] to keep them
+pub fn emit_with<P: AsRef<Path>>(
+    generator: &DefinitionFileGenerator<'_>,
+    out_dir: P,
+) -> Result<Vec<PathBuf>, GenError> {
+    Ok(emit_with_collect(generator, out_dir)?.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Same as [`emit_with`] but also returns the entries that were skipped in each file
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{generator::{Definition, DefinitionFileGenerator, Definitions, Entry}, Type};
+///
+/// let mut definition = Definition::start().value::<String>("name");
+/// // A lone `self` type can't be written as a definition entry
+/// definition.entries.push(Entry::new("invalid", Type::SelfType));
+///
+/// let generator = DefinitionFileGenerator::new(Definitions::start().define("init", definition).finish())
+///     .strict(false);
+///
+/// let dir = std::env::temp_dir().join("mlua-extras-emit-collect");
+/// let written = mlua_extras::build::emit_with_collect(&generator, &dir)?;
+/// assert_eq!(written[0].0, dir.join("init.d.lua"));
+/// assert_eq!(written[0].1.iter().map(|v| v.name.as_str()).collect::<Vec<_>>(), ["invalid"]);
+/// # Ok::<_, mlua_extras::typed::generator::GenError>(())
+/// ```
+pub fn emit_with_collect<P: AsRef<Path>>(
+    generator: &DefinitionFileGenerator<'_>,
+    out_dir: P,
+) -> Result<Vec<(PathBuf, Vec<SkippedEntry>)>, GenError> {
+    let out_dir = out_dir.as_ref();
+    std::fs::create_dir_all(out_dir)?;
+
+    let mut written = Vec::new();
+    for (name, writer) in generator.iter() {
+        let path = out_dir.join(name);
+        let skipped = writer.write_file_collect(&path)?;
+        written.push((path, skipped));
+    }
+    Ok(written)
+}
@@ -95,3 +95,108 @@ macro_rules! function {
         }
     };
 }
+
+/// Add many methods and functions to a [`TypedDataMethods`][crate::typed::TypedDataMethods]
+/// builder using Rust's function syntax
+///
+/// An entry with an untyped receiver before the lua parameter, i.e. `fn name(this, lua)`, is added
+/// with `add_method`, and with `add_method_mut` when the receiver is `mut`. Any other entry,
+/// i.e. `fn name(lua)`, is added with `add_function`. Doc comments are added with `document`.
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::{
+///     methods,
+///     typed::{TypedDataMethods, TypedUserData},
+/// };
+///
+/// struct Counter(usize);
+///
+/// impl TypedUserData for Counter {
+///     fn add_methods<'lua, M: TypedDataMethods<'lua, Self>>(methods: &mut M) {
+///         methods! { methods =>
+///             /// Current count
+///             fn get(this, _lua) -> mlua::Result<usize> {
+///                 Ok(this.0)
+///             }
+///
+///             /// Increment the count by `amount`
+///             fn add(mut this, _lua, amount: usize) {
+///                 this.0 += amount;
+///                 Ok(())
+///             }
+///
+///             /// Name of the type
+///             fn name(_lua) {
+///                 Ok("Counter")
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! methods {
+    { $builder: ident => $($entries: tt)* } => {
+        $crate::methods!(@entry $builder; $($entries)*);
+    };
+    (@entry $builder: ident;) => {};
+    (@entry $builder: ident;
+        $(#[doc = $doc: expr])*
+        fn $name: ident(
+            mut $this: ident, $l: ident
+            $(, $arg: ident : $aty: ty)* $(,)?
+        ) $(-> $ret: ty)? {
+            $($body: tt)*
+        }
+        $($rest: tt)*
+    ) => {
+        $crate::methods!(@doc $builder; $($doc)*);
+        $builder.add_method_mut(stringify!($name), |$l, $this, ($($arg,)*): ($($aty,)*)| $(-> $ret)? {
+            $($body)*
+        });
+        $crate::methods!(@entry $builder; $($rest)*);
+    };
+    (@entry $builder: ident;
+        $(#[doc = $doc: expr])*
+        fn $name: ident(
+            $this: ident, $l: ident
+            $(, $arg: ident : $aty: ty)* $(,)?
+        ) $(-> $ret: ty)? {
+            $($body: tt)*
+        }
+        $($rest: tt)*
+    ) => {
+        $crate::methods!(@doc $builder; $($doc)*);
+        $builder.add_method(stringify!($name), |$l, $this, ($($arg,)*): ($($aty,)*)| $(-> $ret)? {
+            $($body)*
+        });
+        $crate::methods!(@entry $builder; $($rest)*);
+    };
+    (@entry $builder: ident;
+        $(#[doc = $doc: expr])*
+        fn $name: ident(
+            $l: ident
+            $(, $arg: ident : $aty: ty)* $(,)?
+        ) $(-> $ret: ty)? {
+            $($body: tt)*
+        }
+        $($rest: tt)*
+    ) => {
+        $crate::methods!(@doc $builder; $($doc)*);
+        $builder.add_function(stringify!($name), |$l, ($($arg,)*): ($($aty,)*)| $(-> $ret)? {
+            $($body)*
+        });
+        $crate::methods!(@entry $builder; $($rest)*);
+    };
+    (@doc $builder: ident;) => {};
+    (@doc $builder: ident; $($doc: expr)+) => {
+        $builder.document(
+            &[$($doc),+]
+                .iter()
+                .map(|line: &&str| line.strip_prefix(' ').unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    };
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use mlua::{AnyUserData, FromLua, FromLuaMulti, IntoLua, IntoLuaMulti, Lua, Table, UserDataFields};
+use mlua::{AnyUserData, FromLua, FromLuaMulti, IntoLua, IntoLuaMulti, Lua, Table, UserDataFields, Value};
 
 mod macros;
 mod module;
@@ -9,7 +9,7 @@ mod require;
 pub use module::{LuaModule, Module, ModuleBuilder, ModuleFields, ModuleMethods};
 pub use require::Require;
 
-use crate::MaybeSend;
+use crate::{typed::TypedMultiValue, MaybeSend};
 
 /// Adds quality of life helper methods to the [`Lua`] type
 ///
@@ -149,11 +149,142 @@ pub trait LuaExtras {
         R: IntoLuaMulti<'lua>,
         F: Fn(&'lua Lua, A) -> mlua::Result<R> + Send + 'static;
 
+    /// Remove a global variable by setting it to `nil`
+    ///
+    /// Keys don't have to be strings, any value that can be converted into lua can be used. Keys
+    /// that can never be set, `nil` and `NaN`, are ignored since there is nothing to remove.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{mlua::{self, Lua}, extras::LuaExtras};
+    ///
+    /// let lua = Lua::new();
+    /// lua.set_global("name", "value")?;
+    /// lua.set_global(1, true)?;
+    /// assert!(lua.has_global("name")? && lua.has_global(1)?);
+    ///
+    /// lua.unset_global("name")?;
+    /// lua.unset_global(1)?;
+    /// lua.unset_global(mlua::Nil)?;
+    /// assert!(!lua.has_global("name")? && !lua.has_global(1)?);
+    /// # Ok::<_, mlua::Error>(())
+    /// ```
+    fn unset_global<'lua, K: IntoLua<'lua>>(&'lua self, key: K) -> mlua::Result<()>;
+
+    /// Check if a global variable is set to a value other than `nil`
+    ///
+    /// Keys don't have to be strings, any value that can be converted into lua can be used.
+    fn has_global<'lua, K: IntoLua<'lua>>(&'lua self, key: K) -> mlua::Result<bool>;
+
+    /// Build a [`Module`], i.e. any [`TypedModule`][crate::typed::TypedModule], and set it as a global
+    ///
+    /// Same as `lua.set_global(name, M::module())`
+    fn set_global_module<M: Module>(&self, name: &str) -> mlua::Result<()>;
+
+    /// Add a custom module searcher that `require` calls after the builtin searchers
+    ///
+    /// The searcher is called with the module name and should return a loader function for the
+    /// module, or a string explaining why the module wasn't found. This allows serving modules from
+    /// memory or a virtual file system. The searcher is added to `package.searchers`, or to
+    /// `package.loaders` on lua 5.1 and luajit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua::{Lua, Value};
+    /// use mlua_extras::extras::LuaExtras;
+    ///
+    /// let lua = Lua::new();
+    /// lua.add_searcher(|lua, name| match name.as_str() {
+    ///     "greeting" => Ok(Value::Function(lua.load("return 'hello'").into_function()?)),
+    ///     _ => Ok(Value::String(lua.create_string(format!("\n\tno embedded module '{name}'"))?)),
+    /// }).unwrap();
+    ///
+    /// assert_eq!(lua.load("return require('greeting')").eval::<String>().unwrap(), "hello");
+    /// ```
+    fn add_searcher<'lua, F>(&'lua self, searcher: F) -> mlua::Result<()>
+    where
+        F: Fn(&'lua Lua, String) -> mlua::Result<Value<'lua>> + MaybeSend + 'static;
+
     /// Fetch a nested lua value starting from lua's globals
     fn require<'lua, R: FromLua<'lua>>(&'lua self, path: impl AsRef<str>) -> mlua::Result<R>;
+
+    /// Run every `.lua` file under `root` and set the results as a nested table global named `global`
+    ///
+    /// Each file is keyed by its name without the extension and each folder becomes a nested table,
+    /// i.e. `plugins/ui/button.lua` is available as `global.ui.button`. A folder's `init.lua` must
+    /// return a table which becomes the folder's table itself. Files that aren't `.lua` are skipped.
+    fn load_dir(&self, root: impl AsRef<Path>, global: &str) -> mlua::Result<()>;
+
+    /// Load and evaluate a lua expression or chunk returning the typed result
+    ///
+    /// Multiple return values are supported with a tuple, i.e. `lua.eval_typed::<(String, i32)>("return 'a', 1")`
+    fn eval_typed<'lua, R>(&'lua self, expr: impl AsRef<str>) -> mlua::Result<R>
+    where
+        R: FromLuaMulti<'lua> + TypedMultiValue;
+
+    /// Sorted keys of lua's globals
+    ///
+    /// Number keys are converted to strings and any other non-string keys are skipped
+    fn global_keys(&self) -> mlua::Result<Vec<String>>;
+
+    /// Run `f` and get the sorted global keys that exist afterwards but didn't exist before
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua::Lua;
+    /// use mlua_extras::extras::LuaExtras;
+    ///
+    /// let lua = Lua::new();
+    /// let added = lua.globals_added_by(|lua| {
+    ///     lua.load("plugin = { name = 'example' }; function setup() end").exec()
+    /// }).unwrap();
+    ///
+    /// assert_eq!(added, ["plugin", "setup"]);
+    /// ```
+    fn globals_added_by(&self, f: impl FnOnce(&Lua) -> mlua::Result<()>) -> mlua::Result<Vec<String>>;
 }
 
 impl LuaExtras for Lua {
+    fn load_dir(&self, root: impl AsRef<Path>, global: &str) -> mlua::Result<()> {
+        let table = load_dir_table(self, root.as_ref())?;
+        self.globals().set(global, table)
+    }
+
+    fn eval_typed<'lua, R>(&'lua self, expr: impl AsRef<str>) -> mlua::Result<R>
+    where
+        R: FromLuaMulti<'lua> + TypedMultiValue,
+    {
+        self.load(expr.as_ref()).eval::<R>()
+    }
+
+    fn global_keys(&self) -> mlua::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for pair in self.globals().pairs::<Value, Value>() {
+            let (key, _) = pair?;
+            match key {
+                Value::String(key) => keys.push(key.to_string_lossy().to_string()),
+                Value::Integer(key) => keys.push(key.to_string()),
+                Value::Number(key) => keys.push(key.to_string()),
+                _ => {}
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn globals_added_by(&self, f: impl FnOnce(&Lua) -> mlua::Result<()>) -> mlua::Result<Vec<String>> {
+        let before = self.global_keys()?;
+        f(self)?;
+        Ok(self
+            .global_keys()?
+            .into_iter()
+            .filter(|key| before.binary_search(key).is_err())
+            .collect())
+    }
+
     fn set_global<'lua, K, V>(&'lua self, key: K, value: V) -> mlua::Result<()>
     where
         K: IntoLua<'lua>,
@@ -172,6 +303,37 @@ impl LuaExtras for Lua {
         self.globals().set(key, self.create_function(value)?)
     }
 
+    fn unset_global<'lua, K: IntoLua<'lua>>(&'lua self, key: K) -> mlua::Result<()> {
+        match key.into_lua(self)? {
+            Value::Nil => Ok(()),
+            Value::Number(n) if n.is_nan() => Ok(()),
+            key => self.globals().set(key, Value::Nil),
+        }
+    }
+
+    fn has_global<'lua, K: IntoLua<'lua>>(&'lua self, key: K) -> mlua::Result<bool> {
+        Ok(!self.globals().get::<_, Value>(key)?.is_nil())
+    }
+
+    fn set_global_module<M: Module>(&self, name: &str) -> mlua::Result<()> {
+        self.globals().set(name, M::module())
+    }
+
+    fn add_searcher<'lua, F>(&'lua self, searcher: F) -> mlua::Result<()>
+    where
+        F: Fn(&'lua Lua, String) -> mlua::Result<Value<'lua>> + MaybeSend + 'static,
+    {
+        let package = self.globals().get::<_, Table>("package")?;
+        // `package.loaders` was renamed to `package.searchers` in lua 5.2
+        let searchers = match package.get::<_, Option<Table>>("searchers")? {
+            Some(searchers) => searchers,
+            None => package
+                .get::<_, Option<Table>>("loaders")?
+                .ok_or_else(|| mlua::Error::runtime("missing `package.searchers` and `package.loaders`"))?,
+        };
+        searchers.raw_push(self.create_function(searcher)?)
+    }
+
     fn path(&self) -> mlua::Result<String> {
         self.globals()
             .get::<_, Table>("package")?
@@ -353,6 +515,54 @@ impl LuaExtras for Lua {
     }
 }
 
+fn load_dir_table<'lua>(lua: &'lua Lua, dir: &Path) -> mlua::Result<Table<'lua>> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|v| v.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    let init = dir.join("init.lua");
+    let table = if init.is_file() {
+        match lua.load(init.as_path()).eval::<mlua::Value>()? {
+            mlua::Value::Table(table) => table,
+            other => {
+                return Err(mlua::Error::runtime(format!(
+                    "{} must return a table but returned {}",
+                    init.display(),
+                    other.type_name()
+                )))
+            }
+        }
+    } else {
+        lua.create_table()?
+    };
+
+    for path in entries {
+        let name = if path.is_dir() { path.file_name() } else { path.file_stem() };
+        let Some(name) = name.and_then(|v| v.to_str()) else {
+            continue;
+        };
+
+        let value = if path.is_dir() {
+            mlua::Value::Table(load_dir_table(lua, &path)?)
+        } else if path.extension().is_some_and(|v| v == "lua") && path != init {
+            lua.load(path.as_path()).eval::<mlua::Value>()?
+        } else {
+            continue;
+        };
+
+        if table.contains_key(name)? {
+            return Err(mlua::Error::runtime(format!(
+                "duplicate module '{name}' in {}",
+                dir.display()
+            )));
+        }
+        table.set(name, value)?;
+    }
+
+    Ok(table)
+}
+
 /// Helper that combines some of the assignments of fields for UserData
 pub trait UserDataGetSet<'lua, T> {
     /// Combination of [add_field_method_get](mlua::UserDataFields::add_field_method_get) and [add_field_method_set](mlua::UserDataFields::add_field_method_set)
@@ -97,6 +97,26 @@ pub trait ModuleMethods<'lua> {
         F: Fn(&mlua::Lua, mlua::Table<'_>, A) -> mlua::Result<R> + MaybeSend + 'static,
         A: FromLuaMulti<'lua>,
         R: IntoLuaMulti<'lua>;
+
+    #[cfg(feature = "async")]
+    /// Add an async function to this module's table
+    fn add_async_function<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: IntoLua<'lua>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua>,
+        R: IntoLuaMulti<'lua>;
+
+    #[cfg(feature = "async")]
+    /// Add an async method to this module's table
+    fn add_async_method<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: IntoLua<'lua>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua>,
+        R: IntoLuaMulti<'lua>;
 }
 
 /// Builder that construct a module based on the [`Module`] trait
@@ -202,8 +222,8 @@ impl<'lua> ModuleMethods<'lua> for ModuleBuilder<'lua> {
         self.table.set(
             name,
             self.lua
-                .create_function(move |lua, args: mlua::MultiValue| {
-                    let this = mlua::Table::from_lua_multi(args.clone(), lua)?;
+                .create_function(move |lua, mut args: mlua::MultiValue| {
+                    let this = <mlua::Table as mlua::FromLua>::from_lua(args.pop_front().unwrap_or(mlua::Value::Nil), lua)?;
                     let rest = A::from_lua_multi(args, lua)?;
                     function(lua, this, rest)
                 })?,
@@ -229,11 +249,46 @@ impl<'lua> ModuleMethods<'lua> for ModuleBuilder<'lua> {
         meta.set(
             name,
             self.lua
-                .create_function(move |lua, args: mlua::MultiValue| {
-                    let this = mlua::Table::from_lua_multi(args.clone(), lua)?;
+                .create_function(move |lua, mut args: mlua::MultiValue| {
+                    let this = <mlua::Table as mlua::FromLua>::from_lua(args.pop_front().unwrap_or(mlua::Value::Nil), lua)?;
                     let rest = A::from_lua_multi(args, lua)?;
                     function(lua, this, rest)
                 })?,
         )
     }
+
+    #[cfg(feature = "async")]
+    fn add_async_function<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: IntoLua<'lua>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua>,
+        R: IntoLuaMulti<'lua>,
+    {
+        self.table.set(name, self.lua.create_async_function(function)?)
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_method<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: IntoLua<'lua>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua>,
+        R: IntoLuaMulti<'lua>,
+    {
+        self.table.set(
+            name,
+            self.lua
+                .create_async_function(move |lua, mut args: mlua::MultiValue<'lua>| {
+                    // Convert the arguments before awaiting so a bad `self` or argument is
+                    // reported as the call's error
+                    let future = <mlua::Table as mlua::FromLua>::from_lua(args.pop_front().unwrap_or(mlua::Value::Nil), lua)
+                        .and_then(|this| Ok((this, A::from_lua_multi(args, lua)?)))
+                        .map(|(this, rest)| function(lua, this, rest));
+                    async move { future?.await }
+                })?,
+        )
+    }
 }
@@ -2,12 +2,14 @@
 pub mod typed;
 #[cfg(feature="mlua")]
 pub mod extras;
+#[cfg(feature="build")]
+pub mod build;
 
 #[cfg(feature="mlua")]
 pub use mlua;
 
 #[cfg(feature="derive")]
-pub use mlua_extras_derive::{Typed, UserData};
+pub use mlua_extras_derive::{Typed, TypedModule, UserData};
 
 #[cfg(feature = "send")]
 /// Used by the `send` feature
@@ -0,0 +1,70 @@
+use std::ops::{Deref, DerefMut};
+
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+use super::{Type, Typed};
+
+/// Byte data that is passed to and from lua as a `string`
+///
+/// `Vec<u8>` keeps the generic `Vec<I>` type of `integer[]` since `mlua` converts it into a table
+/// of integers. Wrap byte payloads in this type to convert them to lua strings instead.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LuaBytes(pub Vec<u8>);
+
+impl LuaBytes {
+    /// Get the inner bytes
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl Deref for LuaBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for LuaBytes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for LuaBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&[u8]> for LuaBytes {
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<LuaBytes> for Vec<u8> {
+    fn from(value: LuaBytes) -> Self {
+        value.0
+    }
+}
+
+impl<'lua> IntoLua<'lua> for LuaBytes {
+    fn into_lua(self, lua: &'lua Lua) -> mlua::Result<Value<'lua>> {
+        lua.create_string(&self.0).map(Value::String)
+    }
+}
+
+impl<'lua> FromLua<'lua> for LuaBytes {
+    fn from_lua(value: Value<'lua>, lua: &'lua Lua) -> mlua::Result<Self> {
+        let value = mlua::String::from_lua(value, lua)?;
+        Ok(Self(value.as_bytes().to_vec()))
+    }
+}
+
+impl Typed for LuaBytes {
+    fn ty() -> Type {
+        Type::single("string")
+    }
+}
@@ -2,13 +2,13 @@ use mlua::{AnyUserData, FromLua, FromLuaMulti, IntoLua, IntoLuaMulti, Lua, MetaM
 
 use crate::MaybeSend;
 
-use super::{generator::FunctionBuilder, Typed, TypedMultiValue};
+use super::{generator::FunctionBuilder, IntoLuaTypeLiteral, Typed, TypedMultiValue, Visibility};
 
 mod wrapped;
 mod standard;
 
 pub use wrapped::WrappedBuilder;
-pub use standard::TypedClassBuilder;
+pub use standard::{ClassDiff, Member, MemberKind, TypedClassBuilder};
 
 /// Typed variant of [`UserData`]
 pub trait TypedUserData: Sized {
@@ -36,6 +36,9 @@ pub trait TypedUserData: Sized {
 /// Used inside of [`TypedUserData`] to add doc comments to the userdata type itself
 pub trait TypedDataDocumentation<T: TypedUserData> {
     fn add(&mut self, doc: &str) -> &mut Self;
+
+    /// Mark the userdata type as `(exact)` so undeclared fields are flagged
+    fn exact(&mut self, exact: bool) -> &mut Self;
 }
 
 /// Typed variant of [`UserDataFields`]
@@ -266,19 +269,54 @@ pub trait TypedDataMethods<'lua, T> {
         G: Fn(&mut FunctionBuilder<A, R>);
 
     ///Adds documentation to the next method/function that gets added
+    ///
+    /// The doc stays queued until a method/function is added, use
+    /// [`clear_queued_doc`][TypedDataMethods::clear_queued_doc] to discard it
     fn document(&mut self, doc: &str) -> &mut Self;
+
+    /// Adds documentation to the member that was added last instead of the next one, i.e. a
+    /// trailing doc comment
+    fn document_last(&mut self, doc: &str) -> &mut Self;
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    fn queued_doc(&self) -> Option<&str>;
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    fn clear_queued_doc(&mut self) -> &mut Self;
 }
 
 /// Typed variant of [`UserDataMethods`]
 pub trait TypedDataFields<'lua, T> {
     ///Adds documentation to the next field that gets added
+    ///
+    /// The doc stays queued until a field is added, use
+    /// [`clear_queued_doc`][TypedDataFields::clear_queued_doc] to discard it
     fn document(&mut self, doc: &str) -> &mut Self;
 
+    /// Adds documentation to the member that was added last instead of the next one, i.e. a
+    /// trailing doc comment
+    fn document_last(&mut self, doc: &str) -> &mut Self;
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    fn queued_doc(&self) -> Option<&str>;
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    fn clear_queued_doc(&mut self) -> &mut Self;
+
+    /// Set the visibility of a field that was already added, i.e. `--- @field private name type`
+    fn set_visibility(&mut self, name: impl AsRef<str>, visibility: Visibility) -> &mut Self;
+
     /// Typed version of [add_field](mlua::UserDataFields::add_field)
     fn add_field<V>(&mut self, name: impl AsRef<str>, value: V)
     where
         V: IntoLua<'lua> + Clone + 'static + Typed;
 
+    /// Same as [`add_field`][TypedDataFields::add_field] but records the value as a type literal,
+    /// i.e. `--- @field version "1.2.3"`, falling back to the value's type when it isn't renderable
+    fn add_const_field<V>(&mut self, name: impl AsRef<str>, value: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed + IntoLuaTypeLiteral;
+
     /// Typed version of [add_field_method_get](mlua::UserDataFields::add_field_method_get)
     fn add_field_method_get<S, R, M>(&mut self, name: &S, method: M)
     where
@@ -326,7 +364,12 @@ pub trait TypedDataFields<'lua, T> {
         SET: 'static + MaybeSend + Fn(&'lua Lua, AnyUserData<'lua>, A) -> mlua::Result<()>;
 
     /// Typed version of [add_meta_field](mlua::UserDataFields::add_meta_field)
-    fn add_meta_field<R, F>(&mut self, meta: MetaMethod, f: F)
+    fn add_meta_field<V>(&mut self, meta: MetaMethod, value: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed;
+
+    /// Typed version of [add_meta_field_with](mlua::UserDataFields::add_meta_field_with)
+    fn add_meta_field_with<R, F>(&mut self, meta: MetaMethod, f: F)
     where
         F: 'static + MaybeSend + Fn(&'lua Lua) -> mlua::Result<R>,
         R: IntoLua<'lua> + Typed;
@@ -2,15 +2,17 @@ use std::{borrow::Cow, collections::BTreeMap};
 
 use mlua::{AnyUserData, FromLua, FromLuaMulti, IntoLua, IntoLuaMulti, Lua, MetaMethod};
 
-use crate::{typed::{function::Return, generator::FunctionBuilder, Func, Field}, MaybeSend};
+use crate::{typed::{function::Return, generator::{Definition, Entry, FunctionBuilder}, Func, Field, Transient, Type, Visibility}, MaybeSend};
 
-use super::{Typed, TypedDataDocumentation, TypedDataFields, TypedDataMethods, TypedMultiValue, TypedUserData};
+use super::{IntoLuaTypeLiteral, Typed, TypedDataDocumentation, TypedDataFields, TypedDataMethods, TypedMultiValue, TypedUserData};
 
 /// Type information for a lua `class`. This happens to be a [`TypedUserData`]
-#[derive(Default, Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Default, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct TypedClassBuilder {
     pub type_doc: Option<Cow<'static, str>>,
     queued_doc: Option<String>,
+    /// Whether the class is written as `--- @class (exact)`, forbidding undeclared fields
+    pub exact: bool,
 
     pub fields: BTreeMap<Cow<'static, str>, Field>,
     pub static_fields: BTreeMap<Cow<'static, str>, Field>,
@@ -19,6 +21,90 @@ pub struct TypedClassBuilder {
     pub meta_methods: BTreeMap<Cow<'static, str>, Func>,
     pub functions: BTreeMap<Cow<'static, str>, Func>,
     pub meta_functions: BTreeMap<Cow<'static, str>, Func>,
+
+    /// Member that was added last, which [`document_last`][TypedClassBuilder::document_last] documents
+    last_member: Transient<Option<(MemberKind, Cow<'static, str>)>>,
+}
+
+/// Members that differ between a class's type information and its registered userdata
+///
+/// Created with [`TypedClassBuilder::diff_against_runtime`]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ClassDiff {
+    /// Members with type information that can't be found on the registered userdata
+    pub missing_at_runtime: Vec<String>,
+    /// Members of the registered userdata without type information
+    pub missing_from_types: Vec<String>,
+}
+
+impl ClassDiff {
+    /// Whether the type information and the registered userdata have the same members
+    pub fn is_empty(&self) -> bool {
+        self.missing_at_runtime.is_empty() && self.missing_from_types.is_empty()
+    }
+}
+
+/// Collect the names available through a userdata's `__index` and `__newindex` and check which of
+/// the typed names resolve
+///
+/// mlua keeps field getters, setters, and methods in tables captured by the generated `__index`
+/// and `__newindex` functions, so they can only be listed when the `debug` library is loaded.
+/// Typed names are also looked up directly, where a getter erroring on the proxy counts as found.
+const RUNTIME_MEMBERS: &str = r#"
+local proxy, index, newindex, typed = ...
+local names, found = {}, {}
+
+local function collect(value)
+    if type(value) == "table" then
+        for key in pairs(value) do
+            if type(key) == "string" then names[key] = true end
+        end
+    elseif type(value) == "function" and debug ~= nil and debug.getupvalue ~= nil then
+        local i = 1
+        while true do
+            local name, upvalue = debug.getupvalue(value, i)
+            if name == nil then break end
+            if type(upvalue) == "table" then collect(upvalue) end
+            i = i + 1
+        end
+    end
+end
+collect(index)
+collect(newindex)
+
+for _, name in ipairs(typed) do
+    if names[name] then
+        found[name] = true
+    elseif type(index) == "table" then
+        found[name] = index[name] ~= nil
+    elseif type(index) == "function" then
+        local ok, value = pcall(index, proxy, name)
+        found[name] = not ok or value ~= nil
+    end
+end
+
+local listed = {}
+for name in pairs(names) do listed[#listed + 1] = name end
+return listed, found
+"#;
+
+/// The kind of member a class entry was registered as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MemberKind {
+    Field,
+    StaticField,
+    MetaField,
+    Method,
+    MetaMethod,
+    Function,
+    MetaFunction,
+}
+
+/// A reference to a class member's type information
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Member<'a> {
+    Field(&'a Field),
+    Func(&'a Func),
 }
 
 impl TypedClassBuilder {
@@ -29,6 +115,307 @@ impl TypedClassBuilder {
         T::add_methods(&mut gen);
         gen
     }
+
+    /// Compare the member names of the type information for `T` with the userdata mlua registers
+    /// for `T` through [`WrappedBuilder`][super::WrappedBuilder]
+    ///
+    /// Metamethods are compared with the userdata's metatable. Registered members that aren't typed
+    /// are only found when the members are listed in a table or the `debug` library is loaded,
+    /// i.e. with [`Lua::unsafe_new`], since mlua hides field getters, setters, and methods behind
+    /// its `__index` and `__newindex` functions. Both lists in the result are sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua::{Lua, UserData, UserDataFields, UserDataMethods};
+    /// use mlua_extras::typed::{TypedClassBuilder, TypedDataFields, TypedDataMethods, TypedUserData, WrappedBuilder};
+    ///
+    /// struct Counter(i64);
+    /// impl TypedUserData for Counter {
+    ///     fn add_fields<'lua, F: TypedDataFields<'lua, Self>>(fields: &mut F) {
+    ///         fields.add_field_method_get("count", |_, this| Ok(this.0));
+    ///     }
+    ///
+    ///     fn add_methods<'lua, M: TypedDataMethods<'lua, Self>>(methods: &mut M) {
+    ///         methods.add_method_mut("increment", |_, this, ()| {
+    ///             this.0 += 1;
+    ///             Ok(())
+    ///         });
+    ///     }
+    /// }
+    ///
+    /// impl UserData for Counter {
+    ///     fn add_fields<'lua, F: UserDataFields<'lua, Self>>(fields: &mut F) {
+    ///         let mut wrapper = WrappedBuilder::new(fields);
+    ///         <Counter as TypedUserData>::add_fields(&mut wrapper);
+    ///     }
+    ///
+    ///     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+    ///         let mut wrapper = WrappedBuilder::new(methods);
+    ///         <Counter as TypedUserData>::add_methods(&mut wrapper);
+    ///         // Registered without type information
+    ///         methods.add_method("reset", |_, _, ()| Ok(()));
+    ///     }
+    /// }
+    ///
+    /// let lua = unsafe { Lua::unsafe_new() };
+    /// let diff = TypedClassBuilder::diff_against_runtime::<Counter>(&lua).unwrap();
+    /// assert!(diff.missing_at_runtime.is_empty());
+    /// assert_eq!(diff.missing_from_types, ["reset"]);
+    /// ```
+    pub fn diff_against_runtime<T>(lua: &Lua) -> mlua::Result<ClassDiff>
+    where
+        T: TypedUserData + mlua::UserData + 'static,
+    {
+        let types = Self::new::<T>();
+        let proxy = lua.create_proxy::<T>()?;
+        let metatable = proxy.get_metatable()?;
+
+        let typed_members = types
+            .fields
+            .keys()
+            .chain(types.static_fields.keys())
+            .chain(types.methods.keys())
+            .chain(types.functions.keys())
+            .map(|name| name.to_string())
+            .collect::<std::collections::BTreeSet<_>>();
+        let typed_meta = types
+            .meta_fields
+            .keys()
+            .chain(types.meta_methods.keys())
+            .chain(types.meta_functions.keys())
+            .map(|name| name.to_string())
+            .collect::<std::collections::BTreeSet<_>>();
+
+        let (listed, found): (Vec<String>, BTreeMap<String, bool>) = lua
+            .load(RUNTIME_MEMBERS)
+            .set_name("diff_against_runtime")
+            .call((
+                proxy,
+                metatable.get::<mlua::Value>(MetaMethod::Index)?,
+                metatable.get::<mlua::Value>(MetaMethod::NewIndex)?,
+                typed_members.iter().cloned().collect::<Vec<_>>(),
+            ))?;
+
+        // Entries mlua adds to every userdata metatable
+        let generated = [MetaMethod::Index.name(), MetaMethod::NewIndex.name(), MetaMethod::Type.name()];
+        let mut runtime_meta = std::collections::BTreeSet::new();
+        for pair in metatable.pairs::<mlua::Value>() {
+            let (name, _) = pair?;
+            runtime_meta.insert(name);
+        }
+
+        let mut diff = ClassDiff::default();
+        diff.missing_at_runtime.extend(
+            typed_members
+                .iter()
+                .filter(|name| !found.get(name.as_str()).copied().unwrap_or_default())
+                .cloned(),
+        );
+        diff.missing_at_runtime.extend(typed_meta.iter().filter(|name| !runtime_meta.contains(*name)).cloned());
+        diff.missing_from_types.extend(listed.into_iter().filter(|name| !typed_members.contains(name)));
+        diff.missing_from_types.extend(
+            runtime_meta
+                .into_iter()
+                .filter(|name| !typed_meta.contains(name) && !generated.contains(&name.as_str())),
+        );
+        diff.missing_at_runtime.sort();
+        diff.missing_from_types.sort();
+        Ok(diff)
+    }
+
+    /// Iterate the instance fields
+    pub fn fields_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Field)> {
+        self.fields.iter()
+    }
+
+    /// Iterate the static fields
+    pub fn static_fields_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Field)> {
+        self.static_fields.iter()
+    }
+
+    /// Iterate the metatable fields
+    pub fn meta_fields_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Field)> {
+        self.meta_fields.iter()
+    }
+
+    /// Iterate the methods
+    pub fn methods_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Func)> {
+        self.methods.iter()
+    }
+
+    /// Iterate the metatable methods
+    pub fn meta_methods_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Func)> {
+        self.meta_methods.iter()
+    }
+
+    /// Iterate the functions
+    pub fn functions_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Func)> {
+        self.functions.iter()
+    }
+
+    /// Iterate the metatable functions
+    pub fn meta_functions_iter(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Func)> {
+        self.meta_functions.iter()
+    }
+
+    /// Iterate every member tagged with the kind it was registered as
+    ///
+    /// Members are yielded grouped by kind in the order of [`MemberKind`] and sorted by name within a kind
+    pub fn members(&self) -> impl Iterator<Item = (MemberKind, &Cow<'static, str>, Member<'_>)> {
+        fn fields<'a>(
+            kind: MemberKind,
+            map: &'a BTreeMap<Cow<'static, str>, Field>,
+        ) -> impl Iterator<Item = (MemberKind, &'a Cow<'static, str>, Member<'a>)> {
+            map.iter().map(move |(k, v)| (kind, k, Member::Field(v)))
+        }
+
+        fn funcs<'a>(
+            kind: MemberKind,
+            map: &'a BTreeMap<Cow<'static, str>, Func>,
+        ) -> impl Iterator<Item = (MemberKind, &'a Cow<'static, str>, Member<'a>)> {
+            map.iter().map(move |(k, v)| (kind, k, Member::Func(v)))
+        }
+
+        fields(MemberKind::Field, &self.fields)
+            .chain(fields(MemberKind::StaticField, &self.static_fields))
+            .chain(fields(MemberKind::MetaField, &self.meta_fields))
+            .chain(funcs(MemberKind::Method, &self.methods))
+            .chain(funcs(MemberKind::MetaMethod, &self.meta_methods))
+            .chain(funcs(MemberKind::Function, &self.functions))
+            .chain(funcs(MemberKind::MetaFunction, &self.meta_functions))
+    }
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    pub fn queued_doc(&self) -> Option<&str> {
+        self.queued_doc.as_deref()
+    }
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    pub fn clear_queued_doc(&mut self) -> &mut Self {
+        self.queued_doc = None;
+        self
+    }
+
+    /// Attach a doc comment to the member that was added last instead of queuing it for the next
+    /// member. Does nothing if no member was added yet
+    pub fn document_last(&mut self, doc: &str) -> &mut Self {
+        let doc = Some(doc.to_string().into());
+        if let Some((kind, name)) = &self.last_member.0 {
+            let fields = match kind {
+                MemberKind::Field => Some(&mut self.fields),
+                MemberKind::StaticField => Some(&mut self.static_fields),
+                MemberKind::MetaField => Some(&mut self.meta_fields),
+                _ => None,
+            };
+            if let Some(field) = fields.and_then(|v| v.get_mut(name)) {
+                field.doc = doc;
+                return self;
+            }
+
+            let funcs = match kind {
+                MemberKind::Method => Some(&mut self.methods),
+                MemberKind::MetaMethod => Some(&mut self.meta_methods),
+                MemberKind::Function => Some(&mut self.functions),
+                MemberKind::MetaFunction => Some(&mut self.meta_functions),
+                _ => None,
+            };
+            if let Some(func) = funcs.and_then(|v| v.get_mut(name)) {
+                func.doc = doc;
+            }
+        }
+        self
+    }
+
+    /// Name of a member that is being added, remembering it as the member that was added last
+    fn member_name(&mut self, kind: MemberKind, name: &str) -> Cow<'static, str> {
+        let name: Cow<'static, str> = name.to_string().into();
+        self.last_member = Transient(Some((kind, name.clone())));
+        name
+    }
+
+    /// Merge the members of another class into this one. Members of `other` replace members with
+    /// the same name and their docs are appended to this class's docs
+    pub fn merge(&mut self, other: TypedClassBuilder) {
+        self.type_doc = match (self.type_doc.take(), other.type_doc) {
+            (Some(a), Some(b)) => Some(format!("{a}\n{b}").into()),
+            (a, b) => a.or(b),
+        };
+        self.exact |= other.exact;
+
+        self.fields.extend(other.fields);
+        self.static_fields.extend(other.static_fields);
+        self.meta_fields.extend(other.meta_fields);
+        self.methods.extend(other.methods);
+        self.meta_methods.extend(other.meta_methods);
+        self.functions.extend(other.functions);
+        self.meta_functions.extend(other.meta_functions);
+    }
+
+    /// Set the visibility of a field or static field that was already added, i.e.
+    /// `--- @field private name type`. Unknown names are ignored
+    pub fn set_visibility(&mut self, name: impl AsRef<str>, visibility: Visibility) -> &mut Self {
+        let name = name.as_ref();
+        if let Some(field) = self.fields.get_mut(name) {
+            field.visibility = visibility;
+        }
+        if let Some(field) = self.static_fields.get_mut(name) {
+            field.visibility = visibility;
+        }
+        self
+    }
+
+    fn add_meta_field_type(&mut self, meta: MetaMethod, ty: Type) {
+        let name = self.member_name(MemberKind::MetaField, meta.as_ref());
+        self.meta_fields
+            .entry(name)
+            .and_modify(|v| {
+                v.doc = self.queued_doc.take().map(|v| v.into());
+                v.ty.union_with(ty.clone());
+            })
+            .or_insert_with(|| Field {
+                ty,
+                doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
+            });
+    }
+
+    /// Wrap the class in a definition group with a single class entry named `name`
+    pub fn into_definition<'def>(self, name: impl Into<Cow<'def, str>>) -> Definition<'def> {
+        Definition {
+            entries: Vec::from([Entry::new(name, Type::class(self))]),
+            meta: None,
+        }
+    }
+
+    /// Mark the class as `(exact)` so LuaLS flags any undeclared fields
+    pub fn exact(&mut self, exact: bool) -> &mut Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Replace every [`Type::SelfType`][crate::typed::Type::SelfType] placeholder in the class's
+    /// fields and functions with `ty`
+    pub fn resolve_self(&mut self, ty: &Type) {
+        for field in self
+            .fields
+            .values_mut()
+            .chain(self.static_fields.values_mut())
+            .chain(self.meta_fields.values_mut())
+        {
+            field.ty.resolve_self(ty);
+        }
+
+        for func in self
+            .methods
+            .values_mut()
+            .chain(self.meta_methods.values_mut())
+            .chain(self.functions.values_mut())
+            .chain(self.meta_functions.values_mut())
+        {
+            func.resolve_self(ty);
+        }
+    }
 }
 
 impl<T: TypedUserData> TypedDataDocumentation<T> for TypedClassBuilder {
@@ -40,6 +427,10 @@ impl<T: TypedUserData> TypedDataDocumentation<T> for TypedClassBuilder {
         }
         self
     }
+
+    fn exact(&mut self, exact: bool) -> &mut Self {
+        TypedClassBuilder::exact(self, exact)
+    }
 }
 
 impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
@@ -48,20 +439,56 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         self
     }
 
+    fn document_last(&mut self, doc: &str) -> &mut Self {
+        TypedClassBuilder::document_last(self, doc)
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        TypedClassBuilder::queued_doc(self)
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        TypedClassBuilder::clear_queued_doc(self)
+    }
+
+    fn set_visibility(&mut self, name: impl AsRef<str>, visibility: Visibility) -> &mut Self {
+        TypedClassBuilder::set_visibility(self, name, visibility)
+    }
+
     fn add_field<V>(&mut self, name: impl AsRef<str>, _: V)
     where
         V: IntoLua<'lua> + Clone + 'static + Typed,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::StaticField, name.as_ref());
         self.static_fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | V::ty();
+                v.ty.union_with(V::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: V::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
+            });
+    }
+
+    fn add_const_field<V>(&mut self, name: impl AsRef<str>, value: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed + IntoLuaTypeLiteral,
+    {
+        let name = self.member_name(MemberKind::StaticField, name.as_ref());
+        let ty = value.to_type_literal().unwrap_or_else(V::ty);
+        self.static_fields
+            .entry(name)
+            .and_modify(|v| {
+                v.doc = self.queued_doc.take().map(|v| v.into());
+                v.ty.union_with(ty.clone());
+            })
+            .or_insert_with(|| Field {
+                ty,
+                doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -71,16 +498,17 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         A: FromLua<'lua> + Typed,
         F: 'static + MaybeSend + FnMut(&'lua Lua, AnyUserData<'lua>, A) -> mlua::Result<()>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::StaticField, name.as_ref());
         self.static_fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | A::ty();
+                v.ty.union_with(A::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: A::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -90,16 +518,17 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         R: IntoLua<'lua> + Typed,
         F: 'static + MaybeSend + Fn(&'lua Lua, AnyUserData<'lua>) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::StaticField, name.as_ref());
         self.static_fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | R::ty();
+                v.ty.union_with(R::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: R::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -111,16 +540,18 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         GET: 'static + MaybeSend + Fn(&'lua Lua, AnyUserData<'lua>) -> mlua::Result<R>,
         SET: 'static + MaybeSend + Fn(&'lua Lua, AnyUserData<'lua>, A) -> mlua::Result<()>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::StaticField, name.as_ref());
         self.static_fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | A::ty() | R::ty();
+                v.ty.union_with(A::ty());
+                v.ty.union_with(R::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: A::ty() | R::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -130,16 +561,17 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         A: FromLua<'lua> + Typed,
         M: 'static + MaybeSend + FnMut(&'lua Lua, &mut T, A) -> mlua::Result<()>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Field, name.as_ref());
         self.fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | A::ty();
+                v.ty.union_with(A::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: A::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -149,16 +581,17 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         R: IntoLua<'lua> + Typed,
         M: 'static + MaybeSend + Fn(&'lua Lua, &T) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Field, name.as_ref());
         self.fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | R::ty();
+                v.ty.union_with(R::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: R::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
@@ -170,35 +603,34 @@ impl<'lua, T: TypedUserData> TypedDataFields<'lua, T> for TypedClassBuilder {
         GET: 'static + MaybeSend + Fn(&'lua Lua, &T) -> mlua::Result<R>,
         SET: 'static + MaybeSend + Fn(&'lua Lua, &mut T, A) -> mlua::Result<()>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Field, name.as_ref());
         self.fields
             .entry(name)
             .and_modify(|v| {
                 v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | A::ty() | R::ty();
+                v.ty.union_with(A::ty());
+                v.ty.union_with(R::ty());
             })
-            .or_insert(Field {
+            .or_insert_with(|| Field {
                 ty: A::ty() | R::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             });
     }
 
-    fn add_meta_field<R, F>(&mut self, meta: MetaMethod, _: F)
+    fn add_meta_field<V>(&mut self, meta: MetaMethod, _: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed,
+    {
+        self.add_meta_field_type(meta, V::ty());
+    }
+
+    fn add_meta_field_with<R, F>(&mut self, meta: MetaMethod, _: F)
     where
         F: 'static + MaybeSend + Fn(&'lua Lua) -> mlua::Result<R>,
         R: IntoLua<'lua> + Typed,
     {
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
-        self.meta_fields
-            .entry(name)
-            .and_modify(|v| {
-                v.doc = self.queued_doc.take().map(|v| v.into());
-                v.ty = v.ty.clone() | R::ty();
-            })
-            .or_insert(Field {
-                ty: R::ty(),
-                doc: self.queued_doc.take().map(|v| v.into()),
-            });
+        self.add_meta_field_type(meta, R::ty());
     }
 }
 
@@ -208,6 +640,18 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         self
     }
 
+    fn document_last(&mut self, doc: &str) -> &mut Self {
+        TypedClassBuilder::document_last(self, doc)
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        TypedClassBuilder::queued_doc(self)
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        TypedClassBuilder::clear_queued_doc(self)
+    }
+
     fn add_method<S, A, R, M>(&mut self, name: &S, _: M)
     where
         S: ?Sized + AsRef<str>,
@@ -215,12 +659,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         M: 'static + MaybeSend + Fn(&'lua Lua, &T, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -237,12 +684,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -255,12 +705,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         F: 'static + MaybeSend + Fn(&'lua Lua, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -277,12 +730,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -295,12 +751,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         M: 'static + MaybeSend + FnMut(&'lua Lua, &mut T, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -317,12 +776,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -334,12 +796,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         M: 'static + MaybeSend + Fn(&'lua Lua, &T, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaMethod, meta.as_ref());
         self.meta_methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -355,12 +820,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaMethod, meta.as_ref());
         self.meta_methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -376,12 +844,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         MR: std::future::Future<Output = mlua::Result<R>> + 's,
         R: IntoLuaMulti<'lua> + TypedMultiValue,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -401,12 +872,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -422,12 +896,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
             MR: std::future::Future<Output = mlua::Result<R>> + 's,
             R: IntoLuaMulti<'lua> + TypedMultiValue {
         
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -447,12 +924,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Method, name.as_ref());
         self.methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -465,12 +945,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         F: 'static + MaybeSend + FnMut(&'lua Lua, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -487,12 +970,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -504,12 +990,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         F: 'static + MaybeSend + Fn(&'lua Lua, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaFunction, meta.as_ref());
         self.meta_functions.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -525,12 +1014,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, meta.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -545,12 +1037,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         F: 'static + MaybeSend + Fn(&'lua Lua, A) -> FR,
         FR: 'lua + std::future::Future<Output = mlua::Result<R>>,
     {
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -569,12 +1064,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = name.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::Function, name.as_ref());
         self.functions.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -586,12 +1084,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         M: 'static + MaybeSend + FnMut(&'lua Lua, &mut T, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaMethod, meta.as_ref());
         self.meta_methods.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -607,12 +1108,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaMethod, meta.as_ref());
         self.meta_methods.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -624,12 +1128,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         F: 'static + MaybeSend + FnMut(&'lua Lua, A) -> mlua::Result<R>,
     {
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaFunction, meta.as_ref());
         self.meta_functions.insert(
             name,
             Func {
                 params: A::get_types_as_params(),
-                returns: R::get_types().into_iter().map(|ty| Return { doc: None, ty }).collect(),
+                returns: R::get_types().into_iter().map(|ty| Return { doc: None, name: None, ty }).collect(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -645,12 +1152,15 @@ impl<'lua, T: TypedUserData> TypedDataMethods<'lua, T> for TypedClassBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
-        let name: Cow<'static, str> = meta.as_ref().to_string().into();
+        let name = self.member_name(MemberKind::MetaFunction, meta.as_ref());
         self.meta_functions.insert(
             name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
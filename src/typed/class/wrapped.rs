@@ -2,7 +2,7 @@ use mlua::{AnyUserData, FromLua, FromLuaMulti, IntoLua, IntoLuaMulti, Lua, MetaM
 
 use crate::{typed::generator::FunctionBuilder, MaybeSend};
 
-use super::{Typed, TypedDataFields, TypedDataMethods, TypedMultiValue};
+use super::{IntoLuaTypeLiteral, Typed, TypedDataFields, TypedDataMethods, TypedMultiValue, Visibility};
 
 /// Wrapper around a [`UserDataFields`] and [`UserDataMethods`]
 /// to allow [`TypedUserData`] implementations to be used for [`UserData`]
@@ -21,6 +21,22 @@ impl<'lua, 'ctx, T: UserData, U: UserDataFields<'lua, T>> TypedDataFields<'lua,
         self
     }
 
+    fn document_last(&mut self, _doc: &str) -> &mut Self {
+        self
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        None
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        self
+    }
+
+    fn set_visibility(&mut self, _name: impl AsRef<str>, _visibility: Visibility) -> &mut Self {
+        self
+    }
+
     fn add_field<V>(&mut self, name: impl AsRef<str>, value: V)
     where
         V: IntoLua<'lua> + Clone + 'static + Typed,
@@ -28,6 +44,13 @@ impl<'lua, 'ctx, T: UserData, U: UserDataFields<'lua, T>> TypedDataFields<'lua,
         self.0.add_field(name, value)
     }
 
+    fn add_const_field<V>(&mut self, name: impl AsRef<str>, value: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed + IntoLuaTypeLiteral,
+    {
+        self.0.add_field(name, value)
+    }
+
     fn add_field_function_set<S, A, F>(&mut self, name: &S, function: F)
     where
         S: AsRef<str> + ?Sized,
@@ -88,10 +111,17 @@ impl<'lua, 'ctx, T: UserData, U: UserDataFields<'lua, T>> TypedDataFields<'lua,
         self.0.add_field_method_set(name, set);
     }
 
-    fn add_meta_field<R, F>(&mut self, meta: MetaMethod, f: F)
+    fn add_meta_field<V>(&mut self, meta: MetaMethod, value: V)
+    where
+        V: IntoLua<'lua> + Clone + 'static + Typed,
+    {
+        self.0.add_meta_field(meta, value)
+    }
+
+    fn add_meta_field_with<R, F>(&mut self, meta: MetaMethod, f: F)
     where
         F: 'static + MaybeSend + Fn(&'lua Lua) -> mlua::Result<R>,
-        R: IntoLua<'lua>,
+        R: IntoLua<'lua> + Typed,
     {
         self.0.add_meta_field_with(meta, f)
     }
@@ -104,6 +134,18 @@ impl<'lua, 'ctx, T: UserData, U: UserDataMethods<'lua, T>> TypedDataMethods<'lua
         self
     }
 
+    fn document_last(&mut self, _documentation: &str) -> &mut Self {
+        self
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        None
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        self
+    }
+
     fn add_method<S, A, R, M>(&mut self, name: &S, method: M)
     where
         S: ?Sized + AsRef<str>,
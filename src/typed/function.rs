@@ -1,19 +1,23 @@
 use std::{borrow::Cow, marker::PhantomData};
 
-use mlua::{FromLua, FromLuaMulti, Function, IntoLua, IntoLuaMulti, Lua, Value};
+use mlua::{FromLua, FromLuaMulti, Function, IntoLua, IntoLuaMulti, Lua, MultiValue, Value};
 
 use crate::MaybeSend;
 
-use super::{Type, Typed, TypedMultiValue};
+use super::{generator::ParamNaming, IntoDocComment, Type, Typed, TypedMultiValue};
 
 /// A function parameter type representation
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Param {
     pub doc: Option<Cow<'static, str>>,
     ///If the parameter has a name (will default to Param{number} if None)
     pub name: Option<Cow<'static, str>>,
     ///The type of the parameter
     pub(crate) ty: Type,
+    /// Whether the parameter can be omitted, i.e. `--- @param name? type`
+    pub optional: bool,
+    /// Value used when the parameter is omitted. Only used for documentation
+    pub default: Option<Cow<'static, str>>,
 }
 
 impl Param {
@@ -24,24 +28,111 @@ impl Param {
     }
 
     /// Set the parameters doc comment
-    pub fn set_doc(&mut self, doc: impl Into<Cow<'static, str>>) -> &mut Self {
-        self.doc = Some(doc.into());
+    pub fn set_doc(&mut self, doc: impl IntoDocComment) -> &mut Self {
+        self.doc = doc.into_doc_comment();
+        self
+    }
+
+    /// Mark the parameter as optional, i.e. `--- @param name? type`
+    pub fn set_optional(&mut self, optional: bool) -> &mut Self {
+        self.optional = optional;
+        self
+    }
+
+    /// Set the value used when the parameter is omitted. This also marks the parameter as optional
+    pub fn set_default(&mut self, default: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.default = Some(default.into());
+        self.optional = true;
+        self
+    }
+
+    /// Name of the parameter with `?` appended when it is optional, falling back to `naming`
+    pub(crate) fn display_name(&self, index: usize, naming: &ParamNaming) -> String {
+        format!(
+            "{}{}",
+            naming.name_of(self, index),
+            if self.optional { "?" } else { "" }
+        )
+    }
+
+    /// Whether lua can leave the parameter out, filling it with `nil`. This is the case when the
+    /// parameter is optional, variadic, or its type accepts `nil`
+    #[cfg(debug_assertions)]
+    pub(crate) fn can_omit(&self) -> bool {
+        fn accepts_nil(ty: &Type) -> bool {
+            match ty {
+                Type::Single(name) => matches!(name.as_ref(), "nil" | "any" | "unknown"),
+                Type::Union(types) => types.iter().any(accepts_nil),
+                Type::Variadic(_) => true,
+                _ => false,
+            }
+        }
+        self.optional || accepts_nil(&self.ty)
+    }
+
+    /// Doc comment of the parameter including its default value, i.e. `The count (default: 0)`
+    pub(crate) fn display_doc(&self) -> String {
+        match (self.doc.as_deref(), self.default.as_deref()) {
+            (Some(doc), Some(default)) => format!("{doc} (default: `{default}`)"),
+            (None, Some(default)) => format!("(default: `{default}`)"),
+            (doc, None) => doc.unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Override the parameters type, i.e. with [`Type::SelfType`]
+    pub fn set_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
+        self.ty = ty.into();
         self
     }
 }
 
 /// A function parameter type representation
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Return {
     pub doc: Option<Cow<'static, str>>,
+    /// Name of the return, i.e. `ok` in `--- @return boolean ok`
+    pub name: Option<Cow<'static, str>>,
     ///The type of the return
     pub(crate) ty: Type,
 }
 
 impl Return {
-    /// Set the parameters doc comment
-    pub fn set_doc(&mut self, doc: impl Into<Cow<'static, str>>) -> &mut Self {
-        self.doc = Some(doc.into());
+    /// Create a return of the given type with a doc comment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Return, Type};
+    ///
+    /// let returns: Vec<Return> = vec![
+    ///     Return::new(Type::single("boolean"), "Whether the file was written"),
+    ///     Type::single("string").into(),
+    ///     (Type::single("integer"), "Number of bytes written").into(),
+    /// ];
+    /// ```
+    pub fn new(ty: Type, doc: impl IntoDocComment) -> Self {
+        Return {
+            doc: doc.into_doc_comment(),
+            name: None,
+            ty,
+        }
+    }
+
+    /// Set the returns name
+    pub fn set_name(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the returns doc comment
+    pub fn set_doc(&mut self, doc: impl IntoDocComment) -> &mut Self {
+        self.doc = doc.into_doc_comment();
+        self
+    }
+
+    /// Override the return type, i.e. with [`Type::SelfType`] for method chaining
+    pub fn set_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
+        self.ty = ty.into();
         self
     }
 }
@@ -52,6 +143,8 @@ impl<I: Into<Cow<'static, str>>> From<(I, Type)> for Param {
             doc: None,
             name: Some(name.into()),
             ty,
+            optional: false,
+            default: None,
         }
     }
 }
@@ -62,6 +155,28 @@ impl From<Type> for Param {
             doc: None,
             name: None,
             ty: value,
+            optional: false,
+            default: None,
+        }
+    }
+}
+
+impl From<Type> for Return {
+    fn from(value: Type) -> Self {
+        Return {
+            doc: None,
+            name: None,
+            ty: value,
+        }
+    }
+}
+
+impl<D: Into<Cow<'static, str>>> From<(Type, D)> for Return {
+    fn from((ty, doc): (Type, D)) -> Self {
+        Return {
+            doc: Some(doc.into()),
+            name: None,
+            ty,
         }
     }
 }
@@ -179,6 +294,53 @@ where
         self.inner.call::<Params, Response>(params).unwrap()
     }
 
+    /// Same as [`call`][TypedFunction::call] but when the return values can't be converted into
+    /// `Response` the first return value is deserialized into it instead. i.e. a table returned
+    /// from a script into a userdata struct
+    ///
+    /// The original conversion error is returned if deserializing also fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{mlua::{self, FromLua, Lua, UserData, Value}, typed::{Type, Typed, TypedFunction}};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Deserialize)]
+    /// struct Point { x: i32, y: i32 }
+    ///
+    /// impl UserData for Point {}
+    /// impl Typed for Point {
+    ///     fn ty() -> Type { Type::single("Point") }
+    /// }
+    /// impl<'lua> FromLua<'lua> for Point {
+    ///     fn from_lua(value: Value<'lua>, _: &'lua Lua) -> mlua::Result<Self> {
+    ///         value.as_userdata().ok_or_else(|| mlua::Error::runtime("expected Point"))?.borrow::<Self>().map(|v| v.clone())
+    ///     }
+    /// }
+    ///
+    /// let lua = Lua::new();
+    /// let origin: TypedFunction<(), Point> = lua.load("function() return { x = 1, y = 2 } end").eval()?;
+    /// assert_eq!(origin.call_coerced(&lua, ())?, Point { x: 1, y: 2 });
+    /// # Ok::<_, mlua::Error>(())
+    /// ```
+    #[cfg(feature = "serialize")]
+    pub fn call_coerced(&self, lua: &'lua Lua, params: Params) -> mlua::Result<Response>
+    where
+        Response: serde::de::DeserializeOwned,
+    {
+        use mlua::LuaSerdeExt;
+
+        let values = self.inner.call::<Params, MultiValue>(params)?;
+        match Response::from_lua_multi(values.clone(), lua) {
+            Ok(response) => Ok(response),
+            Err(error) => {
+                let value = values.into_iter().next().unwrap_or(Value::Nil);
+                lua.from_value(value).map_err(|_| error)
+            }
+        }
+    }
+
     /// Create a typed function from a rust function.
     ///
     /// This will call [`Lua::create_function`] under the hood
@@ -196,6 +358,117 @@ where
     }
 }
 
+impl<'lua, Params, Response> TypedFunction<'lua, Params, Response>
+where
+    Params: TypedMultiValue,
+    Response: TypedMultiValue,
+{
+    /// Get a reference to the wrapped lua [`Function`]
+    pub fn as_inner(&self) -> &Function<'lua> {
+        &self.inner
+    }
+
+    /// Unwrap the lua [`Function`] dropping the type information
+    pub fn into_inner(self) -> Function<'lua> {
+        self.inner
+    }
+
+    /// Same as [`call`][TypedFunction::call] but with already assembled arguments, i.e. variadic
+    /// arguments forwarded from another lua call.
+    ///
+    /// The arguments are passed to the function as is. This skips the `Params` type checking by
+    /// design; only the result is converted to `Response`.
+    pub fn call_multi(&self, args: MultiValue<'lua>) -> mlua::Result<Response>
+    where
+        Response: FromLuaMulti<'lua>,
+    {
+        self.inner.call::<MultiValue<'lua>, Response>(args)
+    }
+
+    /// Same as [`FromLua::from_lua`] but also validates that the wrapped function accepts every
+    /// required parameter of `Params` when the crate is built with debug assertions.
+    ///
+    /// The arity is read with `debug.getinfo(func, "u")`. The check is skipped when the `debug`
+    /// library isn't loaded or when the runtime doesn't report `nparams` (Lua 5.1, LuaJIT, Luau).
+    /// Vararg lua functions and rust functions are always accepted.
+    ///
+    /// Lua fills missing arguments with `nil` and drops extra ones, so trailing parameters that are
+    /// optional or accept `nil` don't have to be declared by the lua function. Declaring more
+    /// parameters than `Params` is allowed and only logged as a warning with the `log` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{mlua::{self, Lua, Value}, typed::TypedFunction};
+    ///
+    /// // The debug library is required to read the arity
+    /// let lua = unsafe { Lua::unsafe_new() };
+    /// let func = |src: &str| lua.load(src).eval::<Value>();
+    ///
+    /// // Trailing nil-able parameters can be left out
+    /// assert!(TypedFunction::<(i64, Option<i64>), i64>::try_from_lua_checked(func("function(a) return a end")?, &lua).is_ok());
+    /// // Extra lua parameters are filled with nil
+    /// assert!(TypedFunction::<i64, i64>::try_from_lua_checked(func("function(a, b) return a end")?, &lua).is_ok());
+    /// // Required parameters must be declared
+    /// # #[cfg(debug_assertions)]
+    /// assert!(TypedFunction::<(i64, i64), i64>::try_from_lua_checked(func("function(a) return a end")?, &lua).is_err());
+    /// # Ok::<_, mlua::Error>(())
+    /// ```
+    pub fn try_from_lua_checked(value: Value<'lua>, lua: &'lua Lua) -> mlua::Result<Self> {
+        let inner: Function<'lua> = FromLua::from_lua(value, lua)?;
+        #[cfg(debug_assertions)]
+        Self::check_arity(&inner, lua)?;
+        Ok(Self {
+            inner,
+            _p: PhantomData,
+            _r: PhantomData,
+        })
+    }
+
+    #[cfg(debug_assertions)]
+    fn check_arity(func: &Function<'lua>, lua: &'lua Lua) -> mlua::Result<()> {
+        let Ok(debug) = lua.globals().get::<_, mlua::Table>("debug") else {
+            return Ok(());
+        };
+        let Ok(getinfo) = debug.get::<_, Function>("getinfo") else {
+            return Ok(());
+        };
+        let Ok(info) = getinfo.call::<_, mlua::Table>((func.clone(), "u")) else {
+            return Ok(());
+        };
+
+        let (Some(nparams), Some(is_vararg)) = (
+            info.get::<_, Option<usize>>("nparams")?,
+            info.get::<_, Option<bool>>("isvararg")?,
+        ) else {
+            return Ok(());
+        };
+        if is_vararg {
+            return Ok(());
+        }
+
+        let params = Params::get_types_as_params();
+        let required = params.iter().rposition(|p| !p.can_omit()).map_or(0, |i| i + 1);
+        if nparams < required {
+            return Err(mlua::Error::runtime(format!(
+                "typed function expects at least {required} parameter(s) but the lua function accepts {nparams}",
+            )));
+        }
+
+        #[cfg(feature = "log")]
+        {
+            let variadic = params.iter().any(|p| matches!(p.ty, Type::Variadic(_)));
+            if !variadic && nparams > params.len() {
+                log::warn!(
+                    "typed function passes {} parameter(s) but the lua function accepts {nparams}",
+                    params.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<'lua, Params, Response> FromLua<'lua> for TypedFunction<'lua, Params, Response>
 where
     Params: TypedMultiValue,
@@ -210,6 +483,16 @@ where
     }
 }
 
+impl<'lua, Params, Response> From<TypedFunction<'lua, Params, Response>> for Function<'lua>
+where
+    Params: TypedMultiValue,
+    Response: TypedMultiValue,
+{
+    fn from(value: TypedFunction<'lua, Params, Response>) -> Self {
+        value.inner
+    }
+}
+
 impl<'lua, Params, Response> IntoLua<'lua> for TypedFunction<'lua, Params, Response>
 where
     Params: TypedMultiValue,
@@ -228,7 +511,9 @@ where
     fn ty() -> Type {
         Type::Function {
             params: Params::get_types_as_params(),
-            returns: Response::get_types().into_iter().map(|ty| Return { doc: None, ty }).collect(),
+            returns: Response::get_types().into_iter().map(|ty| Return { doc: None, name: None, ty }).collect(),
+            overloads: Vec::new(),
+            usage: Vec::new(),
         }
     }
 }
@@ -0,0 +1,273 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt::Write};
+
+use crate::typed::{function::Return, Field, Func, Param, Type, TypedClassBuilder, TypedModuleBuilder};
+
+use super::{type_file::WriterOptions, Definition, Definitions};
+
+/// Generates human readable markdown api documentation for each [`Definition`]
+///
+/// Each definition group gets an index page, `{name}.md`, listing its functions, values, and aliases
+/// along with links to a page for every class, `{Class}.md`, and module, `{module}.md`. Nested
+/// modules get their own page using their full path, i.e. `{module}.{nested}.md`.
+///
+/// Types that can't be written as a signature are rendered as `any`.
+///
+/// # Example Output
+///
+/// ```md
+/// # Example
+///
+/// Example class documentation
+///
+/// ## Fields
+///
+/// | Name | Type | Description |
+/// | --- | --- | --- |
+/// | `name` | `string` | Name of the example |
+///
+/// ## Methods
+///
+/// ### `Example:run(): boolean`
+///
+/// Run the example returning it's success state
+/// ```
+pub struct MarkdownGenerator<'def> {
+    /// Extension of each markdown file: Default [`.md`]
+    ///
+    /// **IMPORTANT** Must start with a dot
+    extension: String,
+    definitions: Definitions<'def>,
+    options: WriterOptions,
+}
+
+impl<'def> MarkdownGenerator<'def> {
+    /// Create a new generator given a collection of definitions
+    pub fn new(definitions: Definitions<'def>) -> Self {
+        Self {
+            extension: ".md".into(),
+            definitions: definitions.bucket_targets().merge_classes(),
+            options: WriterOptions::default(),
+        }
+    }
+
+    /// Set the extension that each file will end with
+    pub fn ext(mut self, ext: impl AsRef<str>) -> Self {
+        self.extension = ext.as_ref().to_string();
+        self
+    }
+
+    /// Render every page returning the file name and markdown content of each
+    pub fn iter(&self) -> std::vec::IntoIter<(String, String)> {
+        let mut pages = Vec::new();
+        for (name, definition) in self.definitions.iter() {
+            self.write_definition(&mut pages, name, definition);
+        }
+        pages.into_iter()
+    }
+
+    fn file(&self, name: &str) -> String {
+        format!("{name}{}", self.extension)
+    }
+
+    fn write_definition(&self, pages: &mut Vec<(String, String)>, name: &str, definition: &Definition<'_>) {
+        let mut classes = Vec::new();
+        let mut modules = Vec::new();
+        let mut aliases = String::new();
+        let mut functions = String::new();
+        let mut values = String::new();
+
+        for entry in definition.iter() {
+            let doc = entry.doc.as_deref();
+            match &entry.ty {
+                Type::Class(class) => {
+                    classes.push(entry.name.to_string());
+                    pages.push((self.file(&entry.name), self.class_page(&entry.name, doc, class)));
+                }
+                Type::Module(module) => {
+                    modules.push(entry.name.to_string());
+                    self.write_module(pages, &entry.name, doc, module);
+                }
+                Type::Enum(_, types) => {
+                    self.write_alias(&mut aliases, &entry.name, doc, &Type::union(types.clone()))
+                }
+                Type::Alias(ty) => self.write_alias(&mut aliases, &entry.name, doc, ty),
+                Type::Function { params, returns, .. } => {
+                    self.write_func(&mut functions, &entry.name, params, returns, doc)
+                }
+                Type::Value(ty) => self.write_value(&mut values, &entry.name, doc, ty),
+                other => self.write_value(&mut values, &entry.name, doc, other),
+            }
+        }
+
+        let mut page = format!("# {name}\n");
+        let links = |page: &mut String, title: &str, names: &[String]| {
+            if !names.is_empty() {
+                let _ = write!(page, "\n## {title}\n\n");
+                for name in names {
+                    let _ = writeln!(page, "- [{name}]({})", self.file(name));
+                }
+            }
+        };
+        links(&mut page, "Classes", &classes);
+        links(&mut page, "Modules", &modules);
+        for (title, section) in [("Aliases", aliases), ("Functions", functions), ("Values", values)] {
+            if !section.is_empty() {
+                let _ = write!(page, "\n## {title}\n{section}");
+            }
+        }
+
+        pages.push((self.file(name), page));
+    }
+
+    fn write_module(
+        &self,
+        pages: &mut Vec<(String, String)>,
+        path: &str,
+        doc: Option<&str>,
+        module: &TypedModuleBuilder,
+    ) {
+        let mut page = format!("# {path}\n");
+        Self::write_docs(&mut page, &[doc, module.doc.as_deref()]);
+
+        if !module.nested_modules.is_empty() {
+            page.push_str("\n## Modules\n\n");
+            for (name, nested) in module.nested_modules.iter() {
+                let nested_path = format!("{path}.{name}");
+                let _ = writeln!(page, "- [{name}]({})", self.file(&nested_path));
+                self.write_module(pages, &nested_path, None, nested);
+            }
+        }
+
+        self.write_fields(&mut page, "Fields", &module.fields);
+        self.write_funcs(&mut page, "Functions", &format!("{path}."), &module.functions);
+        self.write_funcs(&mut page, "Methods", &format!("{path}:"), &module.methods);
+        self.write_fields(&mut page, "Meta Fields", &module.meta_fields);
+        self.write_funcs(&mut page, "Meta Functions", "", &module.meta_functions);
+        self.write_funcs(&mut page, "Meta Methods", "", &module.meta_methods);
+
+        pages.push((self.file(path), page));
+    }
+
+    fn class_page(&self, name: &str, doc: Option<&str>, class: &TypedClassBuilder) -> String {
+        let mut page = format!("# {name}\n");
+        Self::write_docs(&mut page, &[doc, class.type_doc.as_deref()]);
+
+        self.write_fields(&mut page, "Fields", &class.fields);
+        self.write_fields(&mut page, "Static Fields", &class.static_fields);
+        self.write_funcs(&mut page, "Functions", &format!("{name}."), &class.functions);
+        self.write_funcs(&mut page, "Methods", &format!("{name}:"), &class.methods);
+        self.write_fields(&mut page, "Meta Fields", &class.meta_fields);
+        self.write_funcs(&mut page, "Meta Functions", "", &class.meta_functions);
+        self.write_funcs(&mut page, "Meta Methods", "", &class.meta_methods);
+        page
+    }
+
+    fn write_fields(&self, page: &mut String, title: &str, fields: &BTreeMap<Cow<'static, str>, Field>) {
+        if fields.is_empty() {
+            return;
+        }
+
+        let _ = write!(page, "\n## {title}\n\n| Name | Type | Description |\n| --- | --- | --- |\n");
+        for (name, field) in fields.iter() {
+            let _ = writeln!(
+                page,
+                "| `{name}` | `{}` | {} |",
+                Self::cell(&self.signature(&field.ty)),
+                Self::cell(field.doc.as_deref().unwrap_or_default()),
+            );
+        }
+    }
+
+    fn write_funcs(&self, page: &mut String, title: &str, prefix: &str, funcs: &BTreeMap<Cow<'static, str>, Func>) {
+        if funcs.is_empty() {
+            return;
+        }
+
+        let _ = writeln!(page, "\n## {title}");
+        for (name, func) in funcs.iter() {
+            let name = if func.is_async { format!("async {prefix}{name}") } else { format!("{prefix}{name}") };
+            self.write_func(page, &name, &func.params, &func.returns, func.doc.as_deref());
+        }
+    }
+
+    fn write_func(&self, page: &mut String, name: &str, params: &[Param], returns: &[Return], doc: Option<&str>) {
+        let _ = write!(page, "\n### `{name}({})", self.param_list(params));
+        if !returns.is_empty() {
+            let _ = write!(
+                page,
+                ": {}",
+                returns.iter().map(|v| self.signature(&v.ty)).collect::<Vec<_>>().join(", ")
+            );
+        }
+        page.push_str("`\n");
+        Self::write_docs(page, &[doc]);
+
+        if params.iter().any(|v| v.doc.is_some() || v.default.is_some()) {
+            page.push_str("\n| Param | Type | Description |\n| --- | --- | --- |\n");
+            for (i, param) in params.iter().enumerate() {
+                let _ = writeln!(
+                    page,
+                    "| `{}` | `{}` | {} |",
+                    param.display_name(i, &self.options.param_naming),
+                    Self::cell(&self.param_signature(param)),
+                    Self::cell(&param.display_doc()),
+                );
+            }
+        }
+
+        if returns.iter().any(|v| v.doc.is_some() || v.name.is_some()) {
+            page.push_str("\n| Return | Description |\n| --- | --- |\n");
+            for ret in returns.iter() {
+                let signature = match ret.name.as_deref() {
+                    Some(name) => format!("{name}: {}", self.signature(&ret.ty)),
+                    None => self.signature(&ret.ty),
+                };
+                let _ = writeln!(
+                    page,
+                    "| `{}` | {} |",
+                    Self::cell(&signature),
+                    Self::cell(ret.doc.as_deref().unwrap_or_default()),
+                );
+            }
+        }
+    }
+
+    fn write_alias(&self, page: &mut String, name: &str, doc: Option<&str>, ty: &Type) {
+        let _ = write!(page, "\n### `{name}`\n");
+        Self::write_docs(page, &[doc]);
+        let _ = write!(page, "\n```lua\n{}\n```\n", self.signature(ty));
+    }
+
+    fn write_value(&self, page: &mut String, name: &str, doc: Option<&str>, ty: &Type) {
+        let _ = write!(page, "\n### `{name}`: `{}`\n", self.signature(ty));
+        Self::write_docs(page, &[doc]);
+    }
+
+    fn write_docs(page: &mut String, docs: &[Option<&str>]) {
+        for doc in docs.iter().flatten() {
+            let _ = write!(page, "\n{doc}\n");
+        }
+    }
+
+    fn param_list(&self, params: &[Param]) -> String {
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, v)| format!("{}: {}", v.display_name(i, &self.options.param_naming), self.param_signature(v)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn signature(&self, ty: &Type) -> String {
+        self.options.type_signature(ty).unwrap_or_else(|_| "any".into())
+    }
+
+    fn param_signature(&self, param: &Param) -> String {
+        self.options.param_signature(param).unwrap_or_else(|_| "any".into())
+    }
+
+    /// Escape a value so it can be placed in a markdown table cell
+    fn cell(value: &str) -> String {
+        value.replace('|', "\\|").replace('\n', "<br>")
+    }
+}
@@ -1,20 +1,72 @@
 use std::{
-    borrow::Cow, marker::PhantomData, slice::{Iter, IterMut}, vec::IntoIter
+    borrow::Cow, collections::BTreeSet, hash::{Hash, Hasher}, marker::PhantomData, slice::{Iter, IterMut}, vec::IntoIter
 };
 
-use super::{function::{IntoTypedFunction, Return}, Param, Type, Typed, TypedClassBuilder, TypedModule, TypedModuleBuilder, TypedMultiValue, TypedUserData};
+use super::{function::{IntoTypedFunction, Return}, Documentation, IntoDocComment, Param, Type, TypeVisitor, Typed, TypedClassBuilder, TypedModule, TypedModuleBuilder, TypedMultiValue, TypedUserData};
 
+mod markdown;
 mod type_file;
-pub use type_file::DefinitionFileGenerator;
+pub use markdown::MarkdownGenerator;
+pub use type_file::{ClassFunctionStyle, DefinitionFileGenerator, DefinitionWriter, GenError, LuaVersion, ParamNaming, SkippedEntry, StampOptions};
+pub(crate) use type_file::WriterOptions;
+
+/// FNV-1a hasher. Unlike [`DefaultHasher`][std::hash::DefaultHasher] its algorithm is fixed, so
+/// hashes can be compared between runs
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// Name of a rust type without its module path or generic arguments, i.e. `Wrap` for
+/// `my_crate::Wrap<i32>`
+pub(crate) fn short_type_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split_once('<').map_or(name, |v| v.0);
+    name.rsplit_once("::").map_or(name, |v| v.1)
+}
 
 /// Representation of a type that is defined in the definition file.
 ///
 /// This type has a name and additional documentation that can be displayed
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entry<'def> {
     pub doc: Option<Cow<'def, str>>,
     pub name: Cow<'def, str>,
     pub ty: Type,
+    /// Name of the definition group (file) the entry is written to instead of its own group
+    pub target: Option<Cow<'def, str>>,
+    /// How the entry is written when it is a function type
+    pub function_style: FunctionStyle,
+}
+
+/// How a root level function entry is written in a definition file
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FunctionStyle {
+    /// A `function name(...) end` stub with `--- @param` and `--- @return` annotations
+    #[default]
+    Stub,
+    /// A `--- @type fun(...)` annotation on `name = nil` which doesn't shadow the real implementation
+    ///
+    /// Overloads are added to the type as a union while usage examples and param and return docs
+    /// are dropped. A class entry with this style writes its functions and methods as `--- @field`
+    /// annotations regardless of [`ClassFunctionStyle`]
+    Type,
 }
 
 impl<'def> Entry<'def> {
@@ -24,6 +76,8 @@ impl<'def> Entry<'def> {
             doc: None,
             name: name.into(),
             ty,
+            target: None,
+            function_style: FunctionStyle::default(),
         }
     }
 
@@ -37,8 +91,22 @@ impl<'def> Entry<'def> {
             doc: doc.map(|v| v.into()),
             name: name.into(),
             ty,
+            target: None,
+            function_style: FunctionStyle::default(),
         }
     }
+
+    /// Write the entry to the definition group (file) with the given name instead of its own group
+    pub fn with_target(mut self, target: impl Into<Cow<'def, str>>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Set how the entry is written when it is a function type
+    pub fn with_function_style(mut self, style: FunctionStyle) -> Self {
+        self.function_style = style;
+        self
+    }
 }
 
 /// Builder to add documentation to parameters and return types along with the overall function
@@ -52,6 +120,9 @@ where
     pub doc: Option<Cow<'static, str>>,
     pub params: Vec<Param>,
     pub returns: Vec<Return>,
+    pub overloads: Vec<(Vec<Param>, Vec<Return>)>,
+    pub usage: Vec<Cow<'static, str>>,
+    documentation: Documentation,
     _m: PhantomData<fn(Params) -> Returns>
 }
 
@@ -65,7 +136,10 @@ where
         Self {
             doc: None,
             params: Params::get_types_as_params(),
-            returns: Returns::get_types().into_iter().map(|ty| Return { doc: None, ty }).collect(),
+            returns: Returns::get_types().into_iter().map(|ty| Return { doc: None, name: None, ty }).collect(),
+            overloads: Vec::new(),
+            usage: Vec::new(),
+            documentation: Documentation::default(),
             _m: PhantomData, 
         }        
     }
@@ -77,8 +151,56 @@ where
     Returns: TypedMultiValue,
 {
     /// Set the doc comment for the function type
-    pub fn document(&mut self, doc: impl Into<Cow<'static, str>>) -> &mut Self {
-        self.doc = Some(doc.into());
+    pub fn document(&mut self, doc: impl IntoDocComment) -> &mut Self {
+        self.doc = doc.into_doc_comment();
+        self
+    }
+
+    /// Set the first paragraph of the doc comment
+    ///
+    /// The summary, [`description`][FunctionBuilder::description], and [`tag`][FunctionBuilder::tag]s
+    /// are combined into the doc comment, replacing anything set with [`document`][FunctionBuilder::document]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::FunctionBuilder;
+    ///
+    /// let mut func = FunctionBuilder::<String, ()>::default();
+    /// func.summary("Print a message")
+    ///     .description("The message is written to stdout followed by a newline")
+    ///     .tag("see", "io.write");
+    ///
+    /// assert_eq!(
+    ///     func.doc.as_deref(),
+    ///     Some("Print a message\n\nThe message is written to stdout followed by a newline\n\n@see io.write")
+    /// );
+    /// ```
+    pub fn summary(&mut self, summary: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.documentation.summary = Some(summary.into());
+        self.update_doc()
+    }
+
+    /// Set the paragraph of the doc comment that follows the summary
+    pub fn description(&mut self, description: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.documentation.description = Some(description.into());
+        self.update_doc()
+    }
+
+    /// Add an annotation to the end of the doc comment, i.e. `@see {value}`
+    pub fn tag(&mut self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.documentation.tags.push((name.into(), Some(value.into())));
+        self.update_doc()
+    }
+
+    /// Add an annotation without a value to the end of the doc comment, i.e. `@nodiscard`
+    pub fn flag(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.documentation.tags.push((name.into(), None));
+        self.update_doc()
+    }
+
+    fn update_doc(&mut self) -> &mut Self {
+        self.doc = self.documentation.clone().into_doc_comment();
         self
     }
 
@@ -103,6 +225,37 @@ where
         }
         self
     }
+
+    /// Add a usage example for the function, rendered as a `--- @usage` block
+    ///
+    /// Each call adds a separate example
+    pub fn usage(&mut self, example: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.usage.push(example.into());
+        self
+    }
+
+    /// Add an additional signature for the function, rendered as `--- @overload`
+    pub fn overload<P, R>(&mut self) -> &mut Self
+    where
+        P: TypedMultiValue,
+        R: TypedMultiValue,
+    {
+        self.overload_with::<P, R, _>(|_| {})
+    }
+
+    /// Same as [`overload`][FunctionBuilder::overload] but with a callback to name and document
+    /// the overload's params and returns
+    pub fn overload_with<P, R, F>(&mut self, generator: F) -> &mut Self
+    where
+        P: TypedMultiValue,
+        R: TypedMultiValue,
+        F: Fn(&mut FunctionBuilder<P, R>),
+    {
+        let mut overload = FunctionBuilder::<P, R>::default();
+        generator(&mut overload);
+        self.overloads.push((overload.params, overload.returns));
+        self
+    }
 }
 
 /// Builder for definition entries
@@ -146,7 +299,9 @@ impl<'def> DefinitionBuilder<'def> {
             name,
             Type::Function {
                 params: func.params,
-                returns: func.returns
+                returns: func.returns,
+                overloads: func.overloads,
+                usage: func.usage,
             },
             func.doc,
         ));
@@ -162,67 +317,129 @@ impl<'def> DefinitionBuilder<'def> {
     /// Register a definition entry that is an alias type
     ///
     /// Also add additional documentation
-    pub fn alias_with<S: Into<Cow<'def, str>>>(
+    pub fn alias_with(
         mut self,
         name: impl Into<Cow<'def, str>>,
         ty: Type,
-        doc: Option<S>,
+        doc: impl IntoDocComment,
     ) -> Self {
         self.entries
-            .push(Entry::new_with(name, Type::alias(ty), doc));
+            .push(Entry::new_with(name, Type::alias(ty), doc.into_doc_comment()));
         self
     }
 
     /// Register a definition entry that is a class type
     ///
-    /// The name of the class is the same as the name of the type passed
+    /// The name of the class is the same as the name of the type passed without its generic
+    /// arguments
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{generator::Definition, TypedUserData};
+    ///
+    /// struct Wrap<T>(T);
+    /// impl<T> TypedUserData for Wrap<T> {}
+    ///
+    /// let definition = Definition::start().register_class::<Wrap<i32>>().finish();
+    /// assert_eq!(definition.iter().next().unwrap().name, "Wrap");
+    /// ```
     pub fn register_class<T: TypedUserData>(mut self) -> Self {
-        let name = std::any::type_name::<T>();
         self.entries.push(Entry::new(
-            name.rsplit_once("::").map(|v| v.1).unwrap_or(name),
+            short_type_name::<T>(),
             Type::class(TypedClassBuilder::new::<T>()),
         ));
         self
     }
 
     /// Same as [`register_class`][DefinitionBuilder::register_class] but with additional docs
-    pub fn register_class_with<T: TypedUserData, S: Into<Cow<'def, str>>>(
+    pub fn register_class_with<T: TypedUserData>(
         mut self,
-        doc: Option<S>,
+        doc: impl IntoDocComment,
     ) -> Self {
         self.entries.push(Entry::new_with(
-            std::any::type_name::<T>(),
+            short_type_name::<T>(),
             Type::class(TypedClassBuilder::new::<T>()),
-            doc,
+            doc.into_doc_comment(),
         ));
         self
     }
 
-    /// Register a definition entry that is a class type
-    ///
-    /// The name of the class is the same as the name of the type passed
-    pub fn register_module<T: TypedModule>(mut self, name: impl Into<Cow<'def, str>>) -> Self {
-        self.entries.push(Entry::new(
-            name,
-            // PERF: Ensure that the builder doesn't need it's error bubbled up another layer
-            Type::module(TypedModuleBuilder::new::<T>().unwrap()),
-        ));
+    /// Register a definition entry that is a hand built class type
+    pub fn register_class_builder(
+        mut self,
+        name: impl Into<Cow<'def, str>>,
+        builder: TypedClassBuilder,
+    ) -> Self {
+        self.entries.push(Entry::new(name, Type::class(builder)));
         self
     }
 
+    /// Register a definition entry that is a module type
+    ///
+    /// # Panics
+    ///
+    /// Panics if building the module's type information fails, use
+    /// [`try_register_module`][DefinitionBuilder::try_register_module] to handle the error
+    pub fn register_module<T: TypedModule>(self, name: impl Into<Cow<'def, str>>) -> Self {
+        self.try_register_module::<T>(name)
+            .expect("failed to build the module's type information")
+    }
+
     /// Same as [`register_module`][DefinitionBuilder::register_module] but with additional docs
-    pub fn register_module_with<T: TypedModule, S: Into<Cow<'def, str>>>(
-        mut self,
+    ///
+    /// # Panics
+    ///
+    /// Panics if building the module's type information fails, use
+    /// [`try_register_module_with`][DefinitionBuilder::try_register_module_with] to handle the error
+    pub fn register_module_with<T: TypedModule>(
+        self,
         name: impl Into<Cow<'def, str>>,
-        doc: Option<S>,
+        doc: impl IntoDocComment,
     ) -> Self {
+        self.try_register_module_with::<T>(name, doc)
+            .expect("failed to build the module's type information")
+    }
+
+    /// Register a definition entry that is a module type
+    ///
+    /// Returns the error from building the module's type information, i.e. a module that contains
+    /// itself, instead of panicking
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{mlua, typed::{TypedModule, TypedModuleFields, generator::Definition}};
+    ///
+    /// struct Broken;
+    /// impl TypedModule for Broken {
+    ///     fn add_fields<'lua, F: TypedModuleFields<'lua>>(_: &mut F) -> mlua::Result<()> {
+    ///         Err(mlua::Error::runtime("missing config"))
+    ///     }
+    /// }
+    ///
+    /// assert!(Definition::start().try_register_module::<Broken>("broken").is_err());
+    /// ```
+    pub fn try_register_module<T: TypedModule>(
+        mut self,
+        name: impl Into<Cow<'def, str>>,
+    ) -> mlua::Result<Self> {
+        self.entries.push(Entry::new(name, Type::module(TypedModuleBuilder::new::<T>()?)));
+        Ok(self)
+    }
+
+    /// Same as [`try_register_module`][DefinitionBuilder::try_register_module] but with additional docs
+    pub fn try_register_module_with<T: TypedModule>(
+        mut self,
+        name: impl Into<Cow<'def, str>>,
+        doc: impl IntoDocComment,
+    ) -> mlua::Result<Self> {
         self.entries.push(Entry::new_with(
             name,
-            // PERF: Ensure that the builder doesn't need it's error bubbled up another layer
-            Type::module(TypedModuleBuilder::new::<T>().unwrap()),
-            doc,
+            Type::module(TypedModuleBuilder::new::<T>()?),
+            doc.into_doc_comment(),
         ));
-        self
+        Ok(self)
     }
 
     /// Register a definition entry that is a enum type
@@ -235,7 +452,8 @@ impl<'def> DefinitionBuilder<'def> {
     /// # Example
     ///
     /// ```
-    /// use mlua_extras::{Typed, typed::Type};
+    /// # #[cfg(feature = "derive")] {
+    /// use mlua_extras::{typed::{generator::Definition, Type, Typed}, Typed};
     ///
     /// #[derive(Typed)]
     /// enum Color {
@@ -249,7 +467,12 @@ impl<'def> DefinitionBuilder<'def> {
     ///     Black
     /// }
     ///
-    /// assert!(matches!(Color::ty(), Type::Enum(_, _)))
+    /// assert!(matches!(Color::ty(), Type::Enum(_, _)));
+    ///
+    /// let definition = Definition::start().register_enum::<Color>()?.finish();
+    /// assert_eq!(definition.iter().next().unwrap().name, "Color");
+    /// # }
+    /// # Ok::<_, mlua_extras::mlua::Error>(())
     /// ```
     pub fn register_enum<T: Typed>(mut self) -> mlua::Result<Self> {
         match T::ty() {
@@ -268,14 +491,14 @@ impl<'def> DefinitionBuilder<'def> {
     }
 
     /// Same as [`register`][DefinitionBuilder::register_enum] but with additional docs
-    pub fn register_enum_with<T: Typed, S: Into<Cow<'def, str>>>(
+    pub fn register_enum_with<T: Typed>(
         mut self,
-        doc: Option<S>,
+        doc: impl IntoDocComment,
     ) -> mlua::Result<Self> {
         match T::ty() {
             Type::Enum(name, types) => {
                 self.entries
-                    .push(Entry::new_with(name.clone(), Type::Enum(name, types), doc));
+                    .push(Entry::new_with(name.clone(), Type::Enum(name, types), doc.into_doc_comment()));
             }
             other => {
                 return Err(mlua::Error::runtime(format!(
@@ -294,23 +517,30 @@ impl<'def> DefinitionBuilder<'def> {
     ///
     /// # Example
     /// ```
-    /// user mlua_extras::{typed::{Definitions, TypedUserData}, Typed, UserData};
+    /// # #[cfg(feature = "derive")] {
+    /// use mlua_extras::{
+    ///     typed::{
+    ///         generator::{Definition, DefinitionFileGenerator, Definitions},
+    ///         TypedDataDocumentation, TypedDataFields, TypedUserData,
+    ///     },
+    ///     Typed, UserData,
+    /// };
     ///
-    /// #[derive(UserData, Typed)]
+    /// #[derive(Clone, UserData, Typed)]
     /// struct Example {
     ///     color: String
     /// }
     /// impl TypedUserData for Example {
-    ///     fn add_documentation<F: mlua_extras::typed::TypedDataDocumentation<Self>>(docs: &mut F) {
+    ///     fn add_documentation<F: TypedDataDocumentation<Self>>(docs: &mut F) {
     ///         docs.add("This is an example");
     ///     }
-    ///     
+    ///
     ///     fn add_fields<'lua, F: TypedDataFields<'lua, Self>>(fields: &mut F) {
     ///         fields
     ///             .document("Example field")
     ///             .add_field_method_get_set(
     ///                 "color",
-    ///                 |_lua, this| Ok(this.color),
+    ///                 |_lua, this| Ok(this.color.clone()),
     ///                 |_lua, this, clr: String| {
     ///                     this.color = clr;
     ///                     Ok(())
@@ -319,10 +549,29 @@ impl<'def> DefinitionBuilder<'def> {
     ///     }
     /// }
     ///
-    /// Definitions::generate("init")
-    ///     .register::<Example>("Example")
-    ///     .value::<Example>("example")
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .register_class::<Example>()
+    ///         .value_with::<Example>("example", "The example module")
+    ///     )
     ///     .finish();
+    ///
+    /// let generator = DefinitionFileGenerator::new(definitions);
+    /// let (_, writer) = generator.iter().next().unwrap();
+    /// let mut output = Vec::new();
+    /// writer.write(&mut output).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap().trim_end(), "\
+    /// --- @meta
+    ///
+    /// --- This is an example
+    /// --- @class Example
+    /// --- Example field
+    /// --- @field color string
+    ///
+    /// --- The example module
+    /// --- @type Example
+    /// example = nil");
+    /// # }
     /// ```
     ///
     /// ```lua
@@ -345,13 +594,152 @@ impl<'def> DefinitionBuilder<'def> {
     }
 
     /// Same as [`value`][DefinitionBuilder::value] but with additional docs
-    pub fn value_with<T: Typed, S: Into<Cow<'def, str>>>(
+    pub fn value_with<T: Typed>(
         mut self,
         name: impl Into<Cow<'def, str>>,
-        doc: Option<S>,
+        doc: impl IntoDocComment,
     ) -> Self {
         self.entries
-            .push(Entry::new_with(name, Type::Value(Box::new(T::ty())), doc));
+            .push(Entry::new_with(name, Type::Value(Box::new(T::ty())), doc.into_doc_comment()));
+        self
+    }
+
+    /// Register entries that are written to the definition group (file) named `target` instead of
+    /// this one. The group is created if it isn't already defined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, Definitions};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .value::<String>("name")
+    ///         .target("globals", |b| b.value::<i32>("count"))
+    ///     )
+    ///     .finish();
+    /// ```
+    pub fn target<F>(mut self, target: impl Into<Cow<'def, str>>, generator: F) -> Self
+    where
+        F: FnOnce(DefinitionBuilder<'def>) -> DefinitionBuilder<'def>,
+    {
+        let target = target.into();
+        self.entries.extend(
+            generator(DefinitionBuilder::default())
+                .entries
+                .into_iter()
+                .map(|entry| entry.with_target(target.clone())),
+        );
+        self
+    }
+
+    /// Add fields and functions to an existing global class, i.e. one of lua's standard library tables
+    ///
+    /// Standard library table names are mapped to the class that LuaLS declares for them, i.e.
+    /// `table` to `tablelib` and `string` to `stringlib`, and any other name is used as is. Functions
+    /// are written as `--- @field` annotations so nothing shadows the existing table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use mlua_extras::typed::{
+    ///     generator::{Definition, DefinitionFileGenerator, Definitions},
+    ///     TypedClassBuilder, TypedDataMethods, TypedUserData,
+    /// };
+    ///
+    /// struct TableExtensions;
+    /// impl TypedUserData for TableExtensions {
+    ///     fn add_methods<'lua, M: TypedDataMethods<'lua, Self>>(methods: &mut M) {
+    ///         methods
+    ///             .document("Get the keys of a table")
+    ///             .add_function("keys", |_, table: BTreeMap<String, String>| Ok(table.into_keys().collect::<Vec<_>>()));
+    ///         methods.add_function_with("pick", |_, index: i64| Ok(index.to_string()), |f| {
+    ///             f.usage("table.pick(1)").overload::<(String, String), bool>();
+    ///         });
+    ///     }
+    /// }
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .extend_global_class("table", TypedClassBuilder::new::<TableExtensions>())
+    ///     )
+    ///     .finish();
+    ///
+    /// let generator = DefinitionFileGenerator::new(definitions);
+    /// let (_, writer) = generator.iter().next().unwrap();
+    /// let mut output = Vec::new();
+    /// writer.write(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains(concat!(
+    ///     "--- @usage table.pick(1)\n",
+    ///     "--- @field pick (fun(param0: integer): string) | (fun(param0: string, param1: string): boolean)\n",
+    /// )));
+    /// ```
+    pub fn extend_global_class(mut self, name: impl Into<Cow<'def, str>>, class: TypedClassBuilder) -> Self {
+        let name = name.into();
+        let name = match Self::stdlib_class(&name) {
+            Some(class) => Cow::Borrowed(class),
+            None => name,
+        };
+        self.entries.push(
+            Entry::new(name, Type::class(class)).with_function_style(FunctionStyle::Type),
+        );
+        self
+    }
+
+    /// Name of the class LuaLS declares for a standard library table
+    fn stdlib_class(name: &str) -> Option<&'static str> {
+        Some(match name {
+            "table" => "tablelib",
+            "string" => "stringlib",
+            "package" => "packagelib",
+            "math" => "mathlib",
+            "os" => "oslib",
+            "io" => "iolib",
+            "coroutine" => "coroutinelib",
+            "debug" => "debuglib",
+            "utf8" => "utf8lib",
+            _ => return None,
+        })
+    }
+
+    /// Register entries where functions are written with the given [`FunctionStyle`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, DefinitionFileGenerator, Definitions, FunctionStyle};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .function_style(FunctionStyle::Type, |b| {
+    ///             b.function_with::<String, (), _>("greet", (), |f| {
+    ///                 f.usage("greet('World')");
+    ///             })
+    ///         })
+    ///     )
+    ///     .finish();
+    ///
+    /// let generator = DefinitionFileGenerator::new(definitions);
+    /// let (_, writer) = generator.iter().next().unwrap();
+    /// let mut output = Vec::new();
+    /// writer.write(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains(concat!(
+    ///     "--- @usage greet('World')\n",
+    ///     "--- @type fun(param0: string)\n",
+    ///     "greet = nil\n",
+    /// )));
+    /// ```
+    pub fn function_style<F>(mut self, style: FunctionStyle, generator: F) -> Self
+    where
+        F: FnOnce(DefinitionBuilder<'def>) -> DefinitionBuilder<'def>,
+    {
+        self.entries.extend(
+            generator(DefinitionBuilder::default())
+                .entries
+                .into_iter()
+                .map(|entry| entry.with_function_style(style)),
+        );
         self
     }
 
@@ -359,16 +747,91 @@ impl<'def> DefinitionBuilder<'def> {
     pub fn finish(self) -> Definition<'def> {
         Definition {
             entries: self.entries,
+            meta: None,
         }
     }
 }
 
+/// Object safe collector of definition entries
+///
+/// [`DefinitionBuilder`] is consuming and generic so it can't be used behind `dyn`. Components
+/// that are compiled separately, i.e. plugins, can instead be handed a `&mut dyn DefinitionSink`
+/// to add their types to.
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{Param, Type, TypedClassBuilder};
+/// use mlua_extras::typed::generator::{Definition, DefinitionSink};
+///
+/// fn plugin(sink: &mut dyn DefinitionSink<'static>) {
+///     sink.add_alias("Id".into(), Type::single("integer"));
+///     sink.add_class("Plugin".into(), TypedClassBuilder::default());
+///     sink.add_function(
+///         "load".into(),
+///         vec![Param::from(Type::single("Id"))],
+///         vec![Type::single("Plugin").into()],
+///         Some("Load a plugin by id".into()),
+///     );
+/// }
+///
+/// let mut builder = Definition::start();
+/// plugin(&mut builder);
+/// assert_eq!(builder.entries.len(), 3);
+/// ```
+pub trait DefinitionSink<'def> {
+    /// Add a definition entry
+    fn add_entry(&mut self, entry: Entry<'def>);
+
+    /// Add a definition entry that is a class type
+    fn add_class(&mut self, name: Cow<'def, str>, class: TypedClassBuilder) {
+        self.add_entry(Entry::new(name, Type::class(class)));
+    }
+
+    /// Add a definition entry that is an alias type
+    fn add_alias(&mut self, name: Cow<'def, str>, ty: Type) {
+        self.add_entry(Entry::new(name, Type::alias(ty)));
+    }
+
+    /// Add a definition entry that is a function type with optional documentation
+    fn add_function(
+        &mut self,
+        name: Cow<'def, str>,
+        params: Vec<Param>,
+        returns: Vec<Return>,
+        doc: Option<Cow<'def, str>>,
+    ) {
+        let ty = Type::Function {
+            params,
+            returns,
+            overloads: Vec::new(),
+            usage: Vec::new(),
+        };
+        self.add_entry(Entry::new_with(name, ty, doc));
+    }
+}
+
+impl<'def> DefinitionSink<'def> for Vec<Entry<'def>> {
+    fn add_entry(&mut self, entry: Entry<'def>) {
+        self.push(entry);
+    }
+}
+
+impl<'def> DefinitionSink<'def> for DefinitionBuilder<'def> {
+    fn add_entry(&mut self, entry: Entry<'def>) {
+        self.entries.push(entry);
+    }
+}
+
 /// A named group of definition entries
 ///
 /// This is commonly represented as an individual definition file
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Definition<'def> {
     pub entries: Vec<Entry<'def>>,
+    /// Name written as `--- @meta {name}` so LuaLS keeps the file's globals separate from other
+    /// meta files
+    pub meta: Option<Cow<'def, str>>,
 }
 
 impl<'def> Definition<'def> {
@@ -376,6 +839,25 @@ impl<'def> Definition<'def> {
         DefinitionBuilder::default()
     }
 
+    /// Create a definition group with a single class entry named `name` for the userdata type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{generator::{Definition, DefinitionFileGenerator, Definitions}, TypedUserData};
+    ///
+    /// struct Example;
+    /// impl TypedUserData for Example {}
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("example", Definition::from_userdata::<Example>("Example"))
+    ///     .finish();
+    /// let generator = DefinitionFileGenerator::new(definitions);
+    /// ```
+    pub fn from_userdata<T: TypedUserData>(name: impl Into<Cow<'def, str>>) -> Self {
+        TypedClassBuilder::new::<T>().into_definition(name)
+    }
+
     /// Check if the definition grouping has any entries
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
@@ -384,6 +866,74 @@ impl<'def> Definition<'def> {
     pub fn iter(&self) -> Iter<'def, Entry<'_>> {
         self.entries.iter()
     }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Entry<'def>> {
+        self.entries.iter_mut()
+    }
+
+    /// Hash of the entries and their types that is stable across process runs
+    ///
+    /// This is a hash of the definition data, not of the written file. Writer options and classes
+    /// from other groups also change the written file, and the hash may change between versions of
+    /// this crate. Use [`DefinitionWriter::content_hash`] to only rewrite definition files that
+    /// changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{generator::Definition, TypedClassBuilder, TypedDataFields, TypedUserData};
+    ///
+    /// let a = Definition::start().value::<String>("name").finish();
+    /// let b = Definition::start().value::<String>("name").finish();
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    ///
+    /// // The order members are added in doesn't change the hash
+    /// struct First;
+    /// impl TypedUserData for First {
+    ///     fn add_fields<'lua, F: TypedDataFields<'lua, Self>>(fields: &mut F) {
+    ///         fields.add_field("a", 1);
+    ///         fields.add_field("b", 2);
+    ///     }
+    /// }
+    /// struct Second;
+    /// impl TypedUserData for Second {
+    ///     fn add_fields<'lua, F: TypedDataFields<'lua, Self>>(fields: &mut F) {
+    ///         fields.add_field("b", 2);
+    ///         fields.add_field("a", 1);
+    ///     }
+    /// }
+    ///
+    /// let first = Definition::start().register_class_builder("Example", TypedClassBuilder::new::<First>()).finish();
+    /// let second = Definition::start().register_class_builder("Example", TypedClassBuilder::new::<Second>()).finish();
+    /// assert_eq!(first.content_hash(), second.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Only keep the entries that match the predicate
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{generator::Definition, Type};
+    ///
+    /// let mut definition = Definition::start()
+    ///     .alias("_Private", Type::single("string"))
+    ///     .alias("Public", Type::single("string"))
+    ///     .finish();
+    ///
+    /// definition.retain(|entry| !entry.name.starts_with('_'));
+    /// assert_eq!(definition.iter().count(), 1);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Entry<'def>) -> bool,
+    {
+        self.entries.retain(f)
+    }
 }
 
 /// Generate definition entries and definition groups
@@ -406,6 +956,56 @@ impl<'def> DefinitionsBuilder<'def> {
         self
     }
 
+    /// Same as [`define`][DefinitionsBuilder::define] but the file is written as `--- @meta {meta}`
+    ///
+    /// LuaLS keeps the globals of named meta files separate, so several definition files can be
+    /// shipped without their global scopes colliding
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, DefinitionFileGenerator, Definitions};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define_meta("init", "example", Definition::start().value::<String>("name"))
+    ///     .finish();
+    ///
+    /// let generator = DefinitionFileGenerator::new(definitions);
+    /// let (_, writer) = generator.iter().next().unwrap();
+    /// let mut output = Vec::new();
+    /// writer.write(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().starts_with("--- @meta example\n"));
+    /// ```
+    pub fn define_meta(
+        mut self,
+        name: impl Into<Cow<'def, str>>,
+        meta: impl Into<Cow<'def, str>>,
+        definition: impl Into<Definition<'def>>,
+    ) -> Self {
+        let mut definition = definition.into();
+        definition.meta = Some(meta.into());
+        self.definitions.push((name.into(), definition));
+        self
+    }
+
+    /// Same as [`define`][DefinitionsBuilder::define] but builds the definition group from a closure
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::Definitions;
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define_with("init", |b| b.value::<String>("name"))
+    ///     .finish();
+    /// ```
+    pub fn define_with<F>(self, name: impl Into<Cow<'def, str>>, generator: F) -> Self
+    where
+        F: FnOnce(DefinitionBuilder<'def>) -> DefinitionBuilder<'def>,
+    {
+        self.define(name, generator(Definition::start()))
+    }
+
     /// Finish defining definition groups and collect them
     pub fn finish(self) -> Definitions<'def> {
         Definitions {
@@ -418,12 +1018,16 @@ impl<'def> From<DefinitionBuilder<'def>> for Definition<'def> {
     fn from(value: DefinitionBuilder<'def>) -> Self {
         Definition {
             entries: value.entries,
+            meta: None,
         }
     }
 }
 
 /// A set collection of definition groups
-#[derive(Default, Debug, Clone)]
+///
+/// Definitions only hold type information so they, and the builders used to create them, are
+/// `Send + Sync` and can be built or written from other threads
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Definitions<'def> {
     definitions: Vec<(Cow<'def, str>, Definition<'def>)>,
 }
@@ -443,6 +1047,280 @@ impl<'def> Definitions<'def> {
     pub fn iter_mut(&mut self) -> IterMut<'_, (Cow<'def, str>, Definition<'def>)> {
         self.definitions.iter_mut()
     }
+
+    /// Move every entry with a [`target`][Entry::target] into the definition group with that name,
+    /// creating the group at the end if it doesn't exist. Groups left empty by the move are removed.
+    pub(crate) fn bucket_targets(mut self) -> Self {
+        let mut targeted = Vec::new();
+        self.definitions.retain_mut(|(_, definition)| {
+            let (moved, kept) = std::mem::take(&mut definition.entries)
+                .into_iter()
+                .partition::<Vec<_>, _>(|entry| entry.target.is_some());
+            let emptied = !moved.is_empty() && kept.is_empty();
+            definition.entries = kept;
+            targeted.extend(moved);
+            !emptied
+        });
+
+        for entry in targeted {
+            let target = entry.target.clone().unwrap_or_default();
+            match self.get_mut(&target) {
+                Some(definition) => definition.entries.push(entry),
+                None => self.definitions.push((target, Definition { entries: vec![entry], meta: None })),
+            }
+        }
+        self
+    }
+
+    /// Merge classes registered more than once with the same name in a definition group into the
+    /// first registration so a single `--- @class` is written
+    pub(crate) fn merge_classes(mut self) -> Self {
+        for (_, definition) in self.definitions.iter_mut() {
+            let mut entries: Vec<Entry<'def>> = Vec::new();
+            for entry in std::mem::take(&mut definition.entries) {
+                let existing = entries
+                    .iter_mut()
+                    .find(|v| v.name == entry.name && matches!(v.ty, Type::Class(_)));
+
+                match (existing, entry.ty) {
+                    (Some(existing), Type::Class(class)) => {
+                        if let Type::Class(base) = &mut existing.ty {
+                            base.merge(*class);
+                        }
+                        existing.doc = match (existing.doc.take(), entry.doc) {
+                            (Some(a), Some(b)) => Some(format!("{a}\n{b}").into()),
+                            (a, b) => a.or(b),
+                        };
+                    }
+                    (_, ty) => entries.push(Entry { ty, ..entry }),
+                }
+            }
+            definition.entries = entries;
+        }
+        self
+    }
+
+    /// Names referenced by `Type::Single` that are neither a builtin LuaLS type nor defined as a
+    /// class, enum, or alias in any definition group
+    ///
+    /// Literals, i.e. `"value"` or `3`, and signatures, i.e. `fun()`, are never reported. The
+    /// names are sorted and unique.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Type, generator::{Definition, Definitions}};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .alias("Color", Type::single("string"))
+    ///         .alias("Theme", Type::union([Type::single("Color"), Type::single("Palette")]))
+    ///     )
+    ///     .finish();
+    ///
+    /// assert_eq!(definitions.unresolved_names(), vec!["Palette".to_string()]);
+    /// ```
+    pub fn unresolved_names(&self) -> Vec<String> {
+        self.collect().unresolved
+    }
+
+    /// Check that the definitions are coherent before writing them
+    ///
+    /// Reports names that are referenced but never defined, names that are defined more than
+    /// once, and variadic params that aren't the last param of their signature. Classes that are
+    /// registered more than once in the same group are merged, so they aren't duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Type, generator::{Definition, Definitions, ValidationIssue}};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .alias("Color", Type::single("string"))
+    ///         .alias("Color", Type::single("Palette"))
+    ///         .alias("Handler", Type::callback(
+    ///             [Type::variadic(Type::single("string")), Type::single("integer")],
+    ///             [],
+    ///         ))
+    ///     )
+    ///     .finish();
+    ///
+    /// assert_eq!(definitions.validate(), Err(vec![
+    ///     ValidationIssue::UnresolvedName("Palette".into()),
+    ///     ValidationIssue::DuplicateDefinition { name: "Color".into(), groups: vec!["init".into()] },
+    ///     ValidationIssue::NonTerminalVariadic { entry: "Handler".into(), param: 0 },
+    /// ]));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationIssue>> {
+        let collected = self.collect();
+        let issues = collected
+            .unresolved
+            .into_iter()
+            .map(ValidationIssue::UnresolvedName)
+            .chain(collected.duplicates.into_iter().map(|(name, groups)| {
+                ValidationIssue::DuplicateDefinition { name, groups }
+            }))
+            .chain(collected.variadics.into_iter().map(|(entry, param)| {
+                ValidationIssue::NonTerminalVariadic { entry, param }
+            }))
+            .collect::<Vec<_>>();
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Walk every entry once collecting the unresolved names, duplicate definitions, and
+    /// non-terminal variadic params
+    fn collect(&self) -> Collected {
+        const BUILTINS: &[&str] = &[
+            "nil", "any", "unknown", "boolean", "string", "number", "integer", "function", "table",
+            "thread", "userdata", "lightuserdata", "self", "void", "never",
+        ];
+
+        let mut defined = BTreeSet::new();
+        let mut definitions: Vec<(String, Vec<(String, bool)>)> = Vec::new();
+        let mut collector = Collector::default();
+        for (group, definition) in self.definitions.iter() {
+            for entry in definition.iter() {
+                match &entry.ty {
+                    Type::Class(_) | Type::Alias(_) => {
+                        defined.insert(entry.name.to_string());
+                    }
+                    Type::Enum(name, _) => {
+                        defined.insert(entry.name.to_string());
+                        defined.insert(name.to_string());
+                    }
+                    _ => {}
+                }
+
+                let location = (group.to_string(), matches!(entry.ty, Type::Class(_)));
+                match definitions.iter_mut().find(|(name, _)| name == entry.name.as_ref()) {
+                    Some((_, locations)) => locations.push(location),
+                    None => definitions.push((entry.name.to_string(), vec![location])),
+                }
+
+                collector.entry = entry.name.to_string();
+                entry.ty.accept(&mut collector);
+            }
+        }
+
+        let unresolved = collector
+            .names
+            .into_iter()
+            .filter(|name| {
+                let mut chars = name.chars();
+                chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                    && !matches!(name.as_str(), "true" | "false")
+                    && !BUILTINS.contains(&name.as_str())
+                    && !defined.contains(name)
+            })
+            .collect();
+
+        let duplicates = definitions
+            .into_iter()
+            .filter(|(_, locations)| {
+                let merged = locations
+                    .iter()
+                    .all(|(group, class)| *class && *group == locations[0].0);
+                locations.len() > 1 && !merged
+            })
+            .map(|(name, locations)| {
+                let mut groups = Vec::new();
+                for (group, _) in locations {
+                    if !groups.contains(&group) {
+                        groups.push(group);
+                    }
+                }
+                (name, groups)
+            })
+            .collect();
+
+        Collected {
+            unresolved,
+            duplicates,
+            variadics: collector.variadics,
+        }
+    }
+
+    /// Get a definition group by its name
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&Definition<'def>> {
+        self.definitions
+            .iter()
+            .find_map(|(n, definition)| (n == name.as_ref()).then_some(definition))
+    }
+
+    /// Get a mutable reference to a definition group by its name
+    pub fn get_mut(&mut self, name: impl AsRef<str>) -> Option<&mut Definition<'def>> {
+        self.definitions
+            .iter_mut()
+            .find_map(|(n, definition)| (n == name.as_ref()).then_some(definition))
+    }
+}
+
+/// Problem with the definitions found by [`Definitions::validate`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidationIssue {
+    /// Name referenced by a type that is neither a builtin LuaLS type nor defined as a class,
+    /// enum, or alias
+    UnresolvedName(String),
+    /// Name of an entry that is defined more than once along with the groups that define it
+    DuplicateDefinition { name: String, groups: Vec<String> },
+    /// Variadic param at index `param` that isn't the last param of a signature in `entry`
+    NonTerminalVariadic { entry: String, param: usize },
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnresolvedName(name) => write!(f, "unresolved type name: {name}"),
+            Self::DuplicateDefinition { name, groups } => {
+                write!(f, "'{name}' is defined more than once in: {}", groups.join(", "))
+            }
+            Self::NonTerminalVariadic { entry, param } => {
+                write!(f, "variadic param {param} of '{entry}' is not the last param")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationIssue {}
+
+/// Result of [`Definitions::collect`]
+struct Collected {
+    unresolved: Vec<String>,
+    duplicates: Vec<(String, Vec<String>)>,
+    variadics: Vec<(String, usize)>,
+}
+
+/// Collects every name referenced by a [`Type::Single`] and the variadic params that aren't the
+/// last param of their signature
+#[derive(Default)]
+struct Collector {
+    /// Name of the entry being visited
+    entry: String,
+    names: BTreeSet<String>,
+    variadics: Vec<(String, usize)>,
+}
+
+impl TypeVisitor for Collector {
+    fn visit_single(&mut self, name: &str) {
+        self.names.insert(name.to_string());
+    }
+
+    fn visit_signature(&mut self, params: &[Param], returns: &[Return]) {
+        for (i, param) in params.iter().enumerate() {
+            if matches!(param.ty, Type::Variadic(_)) && i + 1 < params.len() {
+                self.variadics.push((self.entry.clone(), i));
+            }
+            param.ty.accept(self);
+        }
+        returns.iter().for_each(|v| v.ty.accept(self));
+    }
 }
 
 impl<'def> IntoIterator for Definitions<'def> {
@@ -453,3 +1331,20 @@ impl<'def> IntoIterator for Definitions<'def> {
         self.definitions.into_iter()
     }
 }
+
+// The definition model is built and written from other threads, i.e. by parallel build tooling
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[allow(dead_code)]
+    fn definition_model() {
+        assert_send_sync::<Type>();
+        assert_send_sync::<TypedClassBuilder>();
+        assert_send_sync::<TypedModuleBuilder>();
+        assert_send_sync::<Entry<'static>>();
+        assert_send_sync::<DefinitionBuilder<'static>>();
+        assert_send_sync::<Definition<'static>>();
+        assert_send_sync::<DefinitionsBuilder<'static>>();
+        assert_send_sync::<Definitions<'static>>();
+    }
+};
@@ -1,8 +1,10 @@
-use std::{borrow::Cow, path::Path, slice::Iter};
+use std::{borrow::Cow, collections::{BTreeMap, HashMap}, hash::Hasher, path::Path};
 
-use crate::typed::{function::Return, Param, Type, TypedModuleBuilder};
+use crate::typed::{function::Return, Func, Param, Type, TypedModuleBuilder};
 
-use super::{Definition, Definitions};
+use super::{Definition, Definitions, Entry, FunctionStyle, StableHasher};
+
+type GenResult<T> = Result<T, GenError>;
 
 /// Generates a lua definition file for each [`Definition`][`crate::typed::generator::Definition`]
 ///
@@ -10,6 +12,9 @@ use super::{Definition, Definitions};
 /// [LuaLsp](https://github.com/LuaLS/lua-language-server). If there are expose values those are
 /// written as `{name} = nil` with a `--- @type {type}` doc comment above to mark it's value.
 ///
+/// Types are referenced by name so entries may be registered in any order, i.e. a class can be
+/// used as a field type before the class itself is registered.
+///
 /// # Example Output
 ///
 /// ```lua
@@ -30,23 +35,530 @@ pub struct DefinitionFileGenerator<'def> {
     ///
     /// **IMPORTANT** Must start with a dot
     extension: String,
+    /// Extensions of specific definition groups that replace `extension`
+    group_extensions: BTreeMap<String, String>,
     definitions: Definitions<'def>,
+    /// Whether groups are split into type and api files, see [`split_by_kind`][DefinitionFileGenerator::split_by_kind]
+    split: bool,
+    options: WriterOptions,
 }
 
 impl<'def> Default for DefinitionFileGenerator<'def> {
     fn default() -> Self {
         Self {
             extension: ".d.lua".into(),
+            group_extensions: BTreeMap::new(),
             definitions: Definitions::default(),
+            split: false,
+            options: WriterOptions::default(),
+        }
+    }
+}
+
+/// Lua version that the generated definitions target
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LuaVersion {
+    Lua51,
+    Lua52,
+    Lua53,
+    #[default]
+    Lua54,
+    LuaJit,
+}
+
+impl LuaVersion {
+    /// Whether the version has an `integer` subtype of `number` (5.3+)
+    pub fn has_integer(&self) -> bool {
+        matches!(self, Self::Lua53 | Self::Lua54)
+    }
+}
+
+/// How class functions and methods are written
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClassFunctionStyle {
+    /// Written as functions in a `local _Class_{name} = { ... }` table
+    #[default]
+    Table,
+    /// Written as function typed fields on the class, i.e. `--- @field name fun(self: Class): boolean`
+    Field,
+    /// Written both as fields on the class and in the `local _Class_{name}` table
+    Both,
+}
+
+/// What is stamped into each generated file after the banner
+///
+/// Nothing is stamped by default so generated files are reproducible. The timestamp changes on
+/// every generation, so leave it off when the files are compared or checked in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StampOptions {
+    /// Write `--- generated by mlua-extras v{version}`
+    pub version: bool,
+    /// Write `--- generated at {timestamp}` with the current UTC time
+    pub timestamp: bool,
+}
+
+impl StampOptions {
+    /// Stamp lines written into a generated file
+    fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.version {
+            lines.push(format!("--- generated by mlua-extras v{}", env!("CARGO_PKG_VERSION")));
+        }
+        if self.timestamp {
+            lines.push(format!("--- generated at {}", Self::utc_now()));
+        }
+        lines
+    }
+
+    /// Current UTC time formatted as `YYYY-MM-DDTHH:MM:SSZ`
+    fn utc_now() -> String {
+        let seconds = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|v| v.as_secs())
+            .unwrap_or_default();
+        let (days, time) = ((seconds / 86400) as i64, seconds % 86400);
+
+        // Civil date from days since the unix epoch: https://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )
+    }
+}
+
+/// How parameters without a name are named in generated signatures
+#[derive(Debug, Default, Clone, Copy)]
+pub enum ParamNaming {
+    /// Zero based `param{index}`, i.e. `param0`
+    #[default]
+    Param,
+    /// One based `arg{index}`, i.e. `arg1`
+    Arg,
+    /// Every unnamed parameter is written as `_`
+    Underscore,
+    /// Name from the zero based index of the parameter
+    Custom(fn(usize) -> String),
+}
+
+impl ParamNaming {
+    /// Name of `param`, falling back to the naming strategy when it doesn't have one. Variadic
+    /// params are always named `...`
+    pub(crate) fn name_of(&self, param: &Param, index: usize) -> String {
+        if matches!(param.ty, Type::Variadic(_)) {
+            return "...".into();
+        }
+
+        match (param.name.as_deref(), self) {
+            (Some(name), _) => name.to_string(),
+            (None, Self::Param) => format!("param{index}"),
+            (None, Self::Arg) => format!("arg{}", index + 1),
+            (None, Self::Underscore) => "_".into(),
+            (None, Self::Custom(name)) => name(index),
+        }
+    }
+}
+
+/// Options that are shared with each [`DefinitionWriter`]
+#[derive(Debug, Clone)]
+pub(crate) struct WriterOptions {
+    /// Banner written at the top of each file
+    header: Option<String>,
+    /// Write the banner after the `--- @meta` directive instead of before it
+    header_after_meta: bool,
+    /// Version and timestamp written after the banner
+    stamp: StampOptions,
+    /// Write the `--- @meta` directive
+    meta: bool,
+    /// Lua version the types are rendered for
+    lua_version: LuaVersion,
+    /// Largest known length array that is written as a tuple
+    tuple_threshold: usize,
+    /// Largest alias union that is written on a single line
+    alias_union_threshold: usize,
+    /// `--- @type` signatures longer than this are written across multiple indented lines
+    pretty_width: Option<usize>,
+    /// Return the first invalid entry's error instead of skipping the entry
+    strict: bool,
+    /// How class functions and methods are written
+    class_functions: ClassFunctionStyle,
+    /// How parameters without a name are named
+    pub(crate) param_naming: ParamNaming,
+    /// A single level of indentation in generated tables
+    indent: String,
+    /// Classes registered in any definition group so inline class types can be written by name
+    classes: Vec<(String, Type)>,
+    /// Types that are written as another type, checked before any other resolution
+    overrides: HashMap<Type, Type>,
+}
+
+impl WriterOptions {
+    /// `integer` only exists in 5.3+ so it falls back to `number` for older versions
+    fn number_type<'a>(&self, name: &'a str) -> &'a str {
+        if name == "integer" && !self.lua_version.has_integer() {
+            "number"
+        } else {
+            name
+        }
+    }
+
+    pub(super) fn type_signature(&self, ty: &Type) -> GenResult<String> {
+        // The override itself isn't overridden again, but its nested types are
+        let ty = self.overrides.get(ty).unwrap_or(ty);
+        Ok(match ty {
+            Type::Enum(name, _) => name.to_string(),
+            Type::Single(value) => self.number_type(value).to_string(),
+            Type::Tuple(types) => {
+                format!(
+                    "{{ {} }}",
+                    types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| Ok(format!("[{}]: {}", i + 1, self.type_signature(t)?)))
+                        .collect::<GenResult<Vec<_>>>()?
+                        .join(", ")
+                )
+            }
+            Type::Variadic(ty) => {
+                format!("{}...", self.type_signature(ty)?)
+            }
+            Type::Array(ty) => {
+                format!("{{ [{}]: {} }}", self.number_type("integer"), self.type_signature(ty)?)
+            }
+            Type::ArrayOfLen(ty, len) => {
+                if *len > 0 && *len <= self.tuple_threshold {
+                    let ty = self.type_signature(ty)?;
+                    format!(
+                        "{{ {} }}",
+                        (1..=*len)
+                            .map(|i| format!("[{i}]: {ty}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                } else {
+                    self.type_signature(&Type::Array(ty.clone()))?
+                }
+            }
+            Type::Map(key, value) => match key.literal_members() {
+                Some(keys) => {
+                    let value = self.type_signature(value)?;
+                    format!(
+                        "{{ {} }}",
+                        keys.iter()
+                            .map(|key| format!("[{key}]: {value}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+                None => format!(
+                    "{{ [{}]: {} }}",
+                    self.type_signature(key)?,
+                    self.type_signature(value)?
+                ),
+            },
+            Type::Function { params, returns, .. } => self.fun_signature(None, params, returns)?,
+            Type::Union(_) => match ty.clone().simplify() {
+                Type::Union(types) => types
+                    .iter()
+                    .map(|ty| self.type_signature(ty))
+                    .collect::<GenResult<Vec<_>>>()?
+                    .join(" | "),
+                other => self.type_signature(&other)?,
+            },
+            Type::Module(module) => self.module_signature(module)?,
+            Type::Struct(entries) => {
+                format!(
+                    "{{ {} }}",
+                    entries
+                        .iter()
+                        .map(|(k, v)| { Ok(format!("{k}: {}", self.type_signature(v)?)) })
+                        .collect::<GenResult<Vec<_>>>()?
+                        .join(", ")
+                )
+            }
+            // Inline classes are written as a reference to the registered class that is equal,
+            // falling back to the only registered class with the same shape
+            Type::Class(_) => {
+                let find = |eq: fn(&Type, &Type) -> bool| {
+                    let mut matches = self.classes.iter().filter(|(_, class)| eq(class, ty));
+                    (matches.next(), matches.next())
+                };
+                match find(|a, b| a == b) {
+                    (Some((name, _)), None) => name.clone(),
+                    (None, _) => match find(Type::structurally_eq) {
+                        (Some((name, _)), None) => name.clone(),
+                        _ => return Err(GenError::InvalidSignatureType(ty.as_ref().to_string())),
+                    },
+                    _ => return Err(GenError::InvalidSignatureType(ty.as_ref().to_string())),
+                }
+            }
+            Type::SelfType => return Err(GenError::UnresolvedType(ty.as_ref().to_string())),
+            other => return Err(GenError::InvalidSignatureType(other.as_ref().to_string())),
+        })
+    }
+
+    /// Same as [`type_signature`][WriterOptions::type_signature] but table like types, i.e. structs,
+    /// modules, and classes, are broken across multiple lines indented by `indent` spaces per level
+    pub(crate) fn pretty_signature(&self, ty: &Type, indent: usize) -> GenResult<String> {
+        self.pretty_at(ty, indent, 0)
+    }
+
+    fn pretty_at(&self, ty: &Type, indent: usize, depth: usize) -> GenResult<String> {
+        let table = |entries: Vec<(String, String)>| {
+            if entries.is_empty() {
+                return "{}".to_string();
+            }
+            let pad = " ".repeat(indent * (depth + 1));
+            let mut result = String::from("{\n");
+            for (name, ty) in entries {
+                result.push_str(&format!("{pad}{name}: {ty},\n"));
+            }
+            result.push_str(&format!("{}}}", " ".repeat(indent * depth)));
+            result
+        };
+
+        if self.overrides.contains_key(ty) {
+            return self.type_signature(ty);
+        }
+
+        Ok(match ty {
+            Type::Struct(entries) => table(
+                entries
+                    .iter()
+                    .map(|(k, v)| Ok((k.to_string(), self.pretty_at(v, indent, depth + 1)?)))
+                    .collect::<GenResult<Vec<_>>>()?,
+            ),
+            Type::Module(module) => {
+                let mut entries = Vec::new();
+                for (name, field) in module.fields.iter() {
+                    entries.push((name.to_string(), self.pretty_at(&field.ty, indent, depth + 1)?));
+                }
+                entries.extend(self.module_functions(module)?);
+                for (name, nested) in module.nested_modules.iter() {
+                    entries.push((name.to_string(), self.pretty_at(&Type::module(nested.clone()), indent, depth + 1)?));
+                }
+                table(entries)
+            }
+            Type::Class(class) => table(
+                class
+                    .fields
+                    .iter()
+                    .map(|(k, v)| Ok((k.to_string(), self.pretty_at(&v.ty, indent, depth + 1)?)))
+                    .collect::<GenResult<Vec<_>>>()?,
+            ),
+            Type::Array(ty) => {
+                format!("{{ [{}]: {} }}", self.number_type("integer"), self.pretty_at(ty, indent, depth)?)
+            }
+            Type::Map(key, value) if key.literal_members().is_none() => {
+                format!("{{ [{}]: {} }}", self.type_signature(key)?, self.pretty_at(value, indent, depth)?)
+            }
+            Type::Union(_) => match ty.clone().simplify() {
+                Type::Union(types) => types
+                    .iter()
+                    .map(|ty| self.pretty_at(ty, indent, depth))
+                    .collect::<GenResult<Vec<_>>>()?
+                    .join(" | "),
+                other => self.pretty_at(&other, indent, depth)?,
+            },
+            other => self.type_signature(other)?,
+        })
+    }
+
+    /// Type for a `--- @type` annotation, continuing a signature longer than the
+    /// [`pretty_width`][DefinitionFileGenerator::pretty_width] on lines prefixed with `{offset}--- `
+    fn type_annotation(&self, ty: &Type, offset: &str) -> GenResult<String> {
+        let signature = self.type_signature(ty)?;
+        match self.pretty_width {
+            Some(width) if signature.len() > width => Ok(self
+                .pretty_signature(ty, 2)?
+                .replace('\n', &format!("\n{offset}--- "))),
+            _ => Ok(signature),
+        }
+    }
+
+    /// Inline table type for a module's fields, functions, and nested modules, i.e.
+    /// `{ name: string, greet: fun(name: string), nested: { ... } }`
+    fn module_signature(&self, module: &TypedModuleBuilder) -> GenResult<String> {
+        let mut entries = Vec::new();
+        for (name, field) in module.fields.iter() {
+            entries.push(format!("{name}: {}", self.type_signature(&field.ty)?));
+        }
+        for (name, func) in self.module_functions(module)? {
+            entries.push(format!("{name}: {func}"));
+        }
+        for (name, nested) in module.nested_modules.iter() {
+            entries.push(format!("{name}: {}", self.module_signature(nested)?));
+        }
+        Ok(format!("{{ {} }}", entries.join(", ")))
+    }
+
+    /// Inline function types of a module's functions and methods, where methods take `self: table`
+    fn module_functions(&self, module: &TypedModuleBuilder) -> GenResult<Vec<(String, String)>> {
+        let mut entries = Vec::new();
+        for (name, func) in module.functions.iter() {
+            entries.push((name.to_string(), self.fun_signature(None, &func.params, &func.returns)?));
+        }
+        for (name, func) in module.methods.iter() {
+            entries.push((name.to_string(), self.fun_signature(Some("table"), &func.params, &func.returns)?));
+        }
+        Ok(entries)
+    }
+
+    /// Inline function type, i.e. `fun(self: Class, name: string): boolean`
+    fn fun_signature(
+        &self,
+        class: Option<&str>,
+        params: &[Param],
+        returns: &[Return],
+    ) -> GenResult<String> {
+        let mut args = Vec::new();
+        if let Some(class) = class {
+            args.push(format!("self: {class}"));
+        }
+        for (i, param) in params.iter().enumerate() {
+            args.push(format!("{}: {}", param.display_name(i, &self.param_naming), self.param_signature(param)?));
+        }
+
+        let returns = Self::void_returns(returns);
+        Ok(format!(
+            "fun({}){}",
+            args.join(", "),
+            if returns.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    ": {}",
+                    returns
+                        .iter()
+                        .map(|v| self.type_signature(&v.ty))
+                        .collect::<GenResult<Vec<_>>>()?
+                        .join(", ")
+                )
+            }
+        ))
+    }
+
+    /// Type of a parameter. Variadic params are named `...` so only the type of each value is written
+    pub(crate) fn param_signature(&self, param: &Param) -> GenResult<String> {
+        match &param.ty {
+            Type::Variadic(ty) => self.type_signature(ty),
+            ty => self.type_signature(ty),
+        }
+    }
+
+    /// `--- @return` annotation of a return value, i.e. `--- @return string ...` for variadic returns
+    fn return_annotation(&self, ret: &Return) -> GenResult<String> {
+        let doc = ret.doc.as_deref().unwrap_or_default();
+        Ok(match (&ret.ty, ret.name.as_deref()) {
+            (Type::Variadic(ty), _) => format!("--- @return {} ... {doc}", self.type_signature(ty)?),
+            (ty, Some(name)) => format!("--- @return {} {name} {doc}", self.type_signature(ty)?),
+            (ty, None) => format!("--- @return {} {doc}", self.type_signature(ty)?),
+        })
+    }
+
+    /// Returns with a lone undocumented `nil` return removed since it is the same as returning
+    /// nothing, i.e. `fun(a: string)` instead of `fun(a: string): nil`
+    pub(crate) fn void_returns(returns: &[Return]) -> &[Return] {
+        match returns {
+            [ret] if ret.doc.is_none()
+                && ret.name.is_none()
+                && matches!(&ret.ty, Type::Single(name) if name == "nil") => &[],
+            other => other,
+        }
+    }
+}
+
+impl Default for WriterOptions {
+    fn default() -> Self {
+        Self {
+            header: None,
+            header_after_meta: false,
+            stamp: StampOptions::default(),
+            meta: true,
+            lua_version: LuaVersion::default(),
+            tuple_threshold: 0,
+            alias_union_threshold: 3,
+            pretty_width: None,
+            strict: true,
+            class_functions: ClassFunctionStyle::default(),
+            param_naming: ParamNaming::default(),
+            indent: "  ".into(),
+            classes: Vec::new(),
+            overrides: HashMap::new(),
         }
     }
 }
 
 impl<'def> DefinitionFileGenerator<'def> {
     /// Create a new generator given a collection of definitions
+    ///
+    /// Inline class types, i.e. a field typed as [`Type::Class`], are written as a reference to the
+    /// registered class that is equal to it. When no registered class is equal, the only registered
+    /// class with the same shape is used. Writing fails if the reference is ambiguous.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Field, Type, TypedClassBuilder, Visibility};
+    /// use mlua_extras::typed::generator::{Definition, Definitions, DefinitionFileGenerator};
+    ///
+    /// let field = |ty: Type| Field { ty, doc: None, visibility: Visibility::Public };
+    ///
+    /// let mut vec2 = TypedClassBuilder::default();
+    /// vec2.type_doc = Some("A 2d vector".into());
+    /// vec2.fields.insert("x".into(), field(Type::single("number")));
+    /// vec2.fields.insert("y".into(), field(Type::single("number")));
+    ///
+    /// // Same shape as `Vec2`
+    /// let mut size = vec2.clone();
+    /// size.type_doc = Some("Size of a box".into());
+    ///
+    /// let mut player = TypedClassBuilder::default();
+    /// player.fields.insert("position".into(), field(Type::class(vec2.clone())));
+    /// player.fields.insert("size".into(), field(Type::class(size.clone())));
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .register_class_builder("Vec2", vec2)
+    ///         .register_class_builder("Size", size)
+    ///         .register_class_builder("Player", player)
+    ///     )
+    ///     .finish();
+    ///
+    /// let gen = DefinitionFileGenerator::new(definitions);
+    /// let (_, writer) = gen.iter().next().unwrap();
+    /// let mut out = Vec::new();
+    /// writer.write(&mut out).unwrap();
+    /// let out = String::from_utf8(out).unwrap();
+    /// assert!(out.contains("--- @field position Vec2"));
+    /// assert!(out.contains("--- @field size Size"));
+    /// ```
     pub fn new(definitions: Definitions<'def>) -> Self {
+        let definitions = definitions.bucket_targets().merge_classes();
+        let classes = definitions
+            .iter()
+            .flat_map(|(_, definition)| definition.iter())
+            .filter(|entry| matches!(entry.ty, Type::Class(_)))
+            .map(|entry| (entry.name.to_string(), entry.ty.clone()))
+            .collect();
+
         Self {
             definitions,
+            options: WriterOptions {
+                classes,
+                ..Default::default()
+            },
             ..Default::default()
         }
     }
@@ -57,39 +569,433 @@ impl<'def> DefinitionFileGenerator<'def> {
         self
     }
 
+    /// Set the extension of the file for the definition group named `name`, replacing the extension
+    /// set with [`ext`][DefinitionFileGenerator::ext] for that group only
+    ///
+    /// This allows writing runnable stubs, i.e. `.lua`, next to declaration files. With
+    /// [`split_by_kind`][DefinitionFileGenerator::split_by_kind] the group names are
+    /// `{name}.types` and `{name}.api`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{DefinitionFileGenerator, Definitions};
+    ///
+    /// let generator = DefinitionFileGenerator::new(Definitions::start()
+    ///     .define_with("types", |b| b.value::<String>("name"))
+    ///     .define_with("stubs", |b| b.value::<i32>("count"))
+    ///     .finish()
+    /// ).group_ext("stubs", ".lua");
+    ///
+    /// let files = generator.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    /// assert_eq!(files, ["types.d.lua", "stubs.lua"]);
+    /// ```
+    pub fn group_ext(mut self, name: impl Into<String>, ext: impl AsRef<str>) -> Self {
+        self.group_extensions.insert(name.into(), ext.as_ref().to_string());
+        self
+    }
+
+    /// Set a banner that is written at the top of each file
+    ///
+    /// Each line of the banner is written as a doc comment, i.e. `--- DO NOT EDIT`
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.options.header = Some(header.into());
+        self
+    }
+
+    /// Stamp each file with the crate version and/or the time it was generated, written after
+    /// the banner: Default nothing is stamped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, Definitions, DefinitionFileGenerator, StampOptions};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start().value::<String>("name"))
+    ///     .finish();
+    ///
+    /// let gen = DefinitionFileGenerator::new(definitions)
+    ///     .header("DO NOT EDIT")
+    ///     .stamp(StampOptions { version: true, timestamp: false });
+    /// let (_, writer) = gen.iter().next().unwrap();
+    /// let mut out = Vec::new();
+    /// writer.write(&mut out).unwrap();
+    /// assert!(String::from_utf8(out).unwrap().starts_with(&format!(
+    ///     "--- DO NOT EDIT\n--- generated by mlua-extras v{}\n--- @meta\n",
+    ///     env!("CARGO_PKG_VERSION"),
+    /// )));
+    /// ```
+    pub fn stamp(mut self, stamp: StampOptions) -> Self {
+        self.options.stamp = stamp;
+        self
+    }
+
+    /// Write the banner after the `--- @meta` directive instead of before it: Default `false`
+    pub fn header_after_meta(mut self, after: bool) -> Self {
+        self.options.header_after_meta = after;
+        self
+    }
+
+    /// Whether to write the `--- @meta` directive at the top of each file: Default `true`
+    ///
+    /// This is useful when the files are consumed by something other than LuaLS
+    pub fn meta(mut self, meta: bool) -> Self {
+        self.options.meta = meta;
+        self
+    }
+
+    /// Set the lua version the definitions target: Default [`LuaVersion::Lua54`]
+    ///
+    /// `integer` types are written as `number` for versions older than 5.3. `--- @meta` is written
+    /// the same for every version since LuaLS reads the runtime version from its own config.
+    pub fn lua_version(mut self, version: LuaVersion) -> Self {
+        self.options.lua_version = version;
+        self
+    }
+
+    /// Write known length arrays, i.e. `[f32; 3]`, with a length up to `threshold` as tuples so
+    /// the editor knows their length: Default `0`, always written as arrays
+    pub fn tuple_threshold(mut self, threshold: usize) -> Self {
+        self.options.tuple_threshold = threshold;
+        self
+    }
+
+    /// Write alias unions with more than `threshold` members with one `---  | member` line per
+    /// member, the same as enums: Default `3`
+    pub fn alias_union_threshold(mut self, threshold: usize) -> Self {
+        self.options.alias_union_threshold = threshold;
+        self
+    }
+
+    /// Write `--- @type` signatures longer than `width` across multiple indented lines: Default
+    /// `None`, always written on a single line
+    pub fn pretty_width(mut self, width: impl Into<Option<usize>>) -> Self {
+        self.options.pretty_width = width.into();
+        self
+    }
+
+    /// Whether an invalid entry aborts writing the file: Default `true`
+    ///
+    /// When `false` invalid entries are skipped and their errors are returned from
+    /// [`DefinitionWriter::write_collect`] so every problem can be reported at once.
+    /// [`write`][DefinitionWriter::write] and [`write_file`][DefinitionWriter::write_file] drop the
+    /// skipped entries, use [`write_file_collect`][DefinitionWriter::write_file_collect] or
+    /// `par_write_all_collect` to keep them
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    /// Set how class functions and methods are written: Default [`ClassFunctionStyle::Table`]
+    ///
+    /// Metamethods are always written in the `local _Class_{name}` table
+    pub fn class_functions(mut self, style: ClassFunctionStyle) -> Self {
+        self.options.class_functions = style;
+        self
+    }
+
+    /// Write every reference to the type `ty` as `with` instead, i.e. a `Color` class as `string`
+    ///
+    /// Overrides are checked before any other resolution, so registering the same generator
+    /// definitions with different overrides creates output profiles for different consumers.
+    /// Root level entries, i.e. the `--- @class Color` itself, are still written as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Type, generator::{Definition, Definitions, DefinitionFileGenerator}};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start().alias("Palette", Type::array(Type::single("Color"))))
+    ///     .finish();
+    ///
+    /// let gen = DefinitionFileGenerator::new(definitions)
+    ///     .override_type(Type::single("Color"), Type::single("string"));
+    /// let (_, writer) = gen.iter().next().unwrap();
+    /// let mut out = Vec::new();
+    /// writer.write(&mut out).unwrap();
+    /// assert!(String::from_utf8(out).unwrap().contains("--- @alias Palette { [integer]: string }"));
+    /// ```
+    pub fn override_type(mut self, ty: Type, with: Type) -> Self {
+        self.options.overrides.insert(ty, with);
+        self
+    }
+
+    /// Set how parameters without a name are named in signatures: Default [`ParamNaming::Param`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, Definitions, DefinitionFileGenerator, ParamNaming};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start().function::<(String, usize), ()>("greet", |_, _: (String, usize)| Ok(())))
+    ///     .finish();
+    ///
+    /// let gen = DefinitionFileGenerator::new(definitions).param_naming(ParamNaming::Arg);
+    /// let (_, writer) = gen.iter().next().unwrap();
+    /// let mut out = Vec::new();
+    /// writer.write(&mut out).unwrap();
+    /// assert!(String::from_utf8(out).unwrap().contains("function greet(arg1, arg2) end"));
+    /// ```
+    pub fn param_naming(mut self, naming: ParamNaming) -> Self {
+        self.options.param_naming = naming;
+        self
+    }
+
+    /// Set a single level of indentation used in generated module and class tables, i.e. `"\t"`
+    /// or `"    "`: Default two spaces
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.options.indent = indent.into();
+        self
+    }
+
+    /// Write each definition group as two files, `{name}.types` with the classes, enums, and
+    /// aliases, and `{name}.api` with the values, functions, and modules: Default `false`
+    ///
+    /// LuaLS resolves names across every file in the workspace, so values may reference types
+    /// written to the other file. Groups that would be empty are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{generator::{Definition, DefinitionFileGenerator, Definitions}, Type};
+    ///
+    /// let generator = DefinitionFileGenerator::new(Definitions::start()
+    ///     .define("init", Definition::start()
+    ///         .alias("Name", Type::single("string"))
+    ///         .value::<String>("name")
+    ///     )
+    ///     .define("config", Definition::start().value::<bool>("debug"))
+    ///     .finish()
+    /// ).split_by_kind(true);
+    ///
+    /// let files = generator.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    /// assert_eq!(files, ["init.types.d.lua", "init.api.d.lua", "config.api.d.lua"]);
+    /// ```
+    pub fn split_by_kind(mut self, split: bool) -> Self {
+        self.split = split;
+        self
+    }
+
     pub fn iter(&self) -> DefinitionFileIter<'_> {
-        DefinitionFileIter {
-            extension: self.extension.clone(),
-            definitions: self.definitions.iter(),
+        let kinds: &[Option<SplitKind>] = if self.split {
+            &[Some(SplitKind::Types), Some(SplitKind::Api)]
+        } else {
+            &[None]
+        };
+
+        let mut files = Vec::new();
+        for (name, definition) in self.definitions.iter() {
+            for kind in kinds.iter().copied() {
+                let writer = DefinitionWriter { definition, options: &self.options, kind };
+                let name = match kind {
+                    Some(_) if writer.entries().next().is_none() => continue,
+                    Some(kind) => format!("{name}.{}", kind.name()),
+                    None => name.to_string(),
+                };
+                let extension = self.group_extensions.get(&name).unwrap_or(&self.extension);
+                files.push((format!("{name}{extension}"), writer));
+            }
         }
+
+        DefinitionFileIter { files: files.into_iter() }
+    }
+
+    /// Write each definition group into `dir` on its own thread returning the paths of the
+    /// written files
+    ///
+    /// Each thread owns the [`DefinitionWriter`] for its group. The first error is returned after
+    /// every thread has finished.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, Definitions, DefinitionFileGenerator};
+    ///
+    /// let definitions = Definitions::start()
+    ///     .define("init", Definition::start().value::<String>("name"))
+    ///     .define("api", Definition::start().value::<usize>("count"))
+    ///     .finish();
+    ///
+    /// let dir = std::env::temp_dir().join("mlua-extras-par-write");
+    /// let written = DefinitionFileGenerator::new(definitions).par_write_all(&dir).unwrap();
+    /// assert_eq!(written, [dir.join("init.d.lua"), dir.join("api.d.lua")]);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_write_all<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<std::path::PathBuf>, GenError> {
+        Ok(self.par_write_all_collect(dir)?.into_iter().map(|(path, _)| path).collect())
+    }
+
+    /// Same as [`par_write_all`][DefinitionFileGenerator::par_write_all] but also returns the
+    /// entries that were skipped in each file, see [`DefinitionWriter::write_collect`]
+    #[cfg(feature = "parallel")]
+    pub fn par_write_all_collect<P: AsRef<Path>>(
+        &self,
+        dir: P,
+    ) -> Result<Vec<(std::path::PathBuf, Vec<SkippedEntry>)>, GenError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        std::thread::scope(|scope| {
+            let handles = self
+                .iter()
+                .map(|(name, writer)| {
+                    let path = dir.join(name);
+                    scope.spawn(move || writer.write_file_collect(&path).map(|skipped| (path, skipped)))
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("definition writer thread panicked"))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect()
+        })
     }
 }
 
 pub struct DefinitionFileIter<'def> {
-    extension: String,
-    definitions: Iter<'def, (Cow<'def, str>, Definition<'def>)>,
+    files: std::vec::IntoIter<(String, DefinitionWriter<'def>)>,
 }
 
 impl<'def> Iterator for DefinitionFileIter<'def> {
     type Item = (String, DefinitionWriter<'def>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.definitions.next().map(|v| {
-            (
-                format!("{}{}", v.0, self.extension),
-                DefinitionWriter { definition: &v.1 },
-            )
-        })
+        self.files.next()
     }
 }
 
+/// Half of a definition group that is written to its own file with
+/// [`split_by_kind`][DefinitionFileGenerator::split_by_kind]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitKind {
+    /// Classes, enums, and aliases
+    Types,
+    /// Values, functions, and modules
+    Api,
+}
+
+impl SplitKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Types => "types",
+            Self::Api => "api",
+        }
+    }
+
+    fn contains(&self, entry: &Entry) -> bool {
+        let is_type = matches!(entry.ty, Type::Class(_) | Type::Enum(..) | Type::Alias(_));
+        is_type == (*self == Self::Types)
+    }
+}
+
+/// Error produced while generating a definition file
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{generator::GenError, Type};
+///
+/// let error = Type::SelfType.render_pretty(2).unwrap_err();
+/// assert!(matches!(error.root(), GenError::UnresolvedType(_)));
+/// ```
+#[derive(Debug)]
+pub enum GenError {
+    /// A placeholder type, i.e. [`Type::SelfType`], that was never resolved to a concrete type
+    UnresolvedType(String),
+    /// A type that can't be written as a root level definition entry
+    InvalidRootType(String),
+    /// A type that can't be written inline as a type signature, i.e. a class
+    InvalidSignatureType(String),
+    /// Writing the generated definitions failed
+    Io(std::io::Error),
+    /// Error raised while writing the definition entry with the given name
+    Entry {
+        /// Name of the entry
+        name: String,
+        error: Box<GenError>,
+    },
+}
+
+impl GenError {
+    /// Error without the [`Entry`][GenError::Entry] context that was added around it
+    pub fn root(&self) -> &GenError {
+        match self {
+            Self::Entry { error, .. } => error.root(),
+            other => other,
+        }
+    }
+}
+
+impl std::fmt::Display for GenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnresolvedType(name) => write!(f, "unresolved type: {name}"),
+            Self::InvalidRootType(name) => write!(f, "invalid root level type: {name}"),
+            Self::InvalidSignatureType(name) => write!(f, "type cannot be a type signature: {name}"),
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Entry { name, error } => write!(f, "while writing '{name}': {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Entry { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GenError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<GenError> for mlua::Error {
+    fn from(value: GenError) -> Self {
+        mlua::Error::external(value)
+    }
+}
+
+/// Definition entry that was skipped because it couldn't be written
+#[derive(Debug)]
+pub struct SkippedEntry {
+    /// Name of the entry
+    pub name: String,
+    pub error: GenError,
+}
+
+impl std::fmt::Display for SkippedEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.error)
+    }
+}
+
+impl std::error::Error for SkippedEntry {}
+
 pub struct DefinitionWriter<'def> {
     definition: &'def Definition<'def>,
+    options: &'def WriterOptions,
+    /// Only write the entries of this kind when the group is split
+    kind: Option<SplitKind>,
 }
 
 impl DefinitionWriter<'_> {
+    /// Entries of the definition group that are written to this file
+    fn entries(&self) -> impl Iterator<Item = &Entry<'_>> {
+        self.definition.iter().filter(|entry| self.kind.is_none_or(|kind| kind.contains(entry)))
+    }
+
     /// Write the full definition group to a specified file
-    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> mlua::Result<()> {
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> GenResult<()> {
         let mut file = std::fs::OpenOptions::new()
             .create(true)
             .truncate(true)
@@ -98,233 +1004,427 @@ impl DefinitionWriter<'_> {
         self.write(&mut file)
     }
 
+    /// Same as [`write_file`][DefinitionWriter::write_file] but returns the error of each entry
+    /// that was skipped, see [`write_collect`][DefinitionWriter::write_collect]
+    pub fn write_file_collect<P: AsRef<Path>>(&self, path: P) -> GenResult<Vec<SkippedEntry>> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+        self.write_collect(&mut file)
+    }
+
+    /// Hash of the written definition group that is stable across process runs
+    ///
+    /// Compare it with the hash from a previous run to only rewrite definition files that changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::generator::{Definition, DefinitionFileGenerator, Definitions};
+    ///
+    /// let definitions = || Definitions::start()
+    ///     .define("init", Definition::start().value::<String>("name"))
+    ///     .finish();
+    ///
+    /// let hash = |generator: DefinitionFileGenerator| generator.iter().next().unwrap().1.content_hash().unwrap();
+    /// let plain = hash(DefinitionFileGenerator::new(definitions()));
+    /// assert_eq!(plain, hash(DefinitionFileGenerator::new(definitions())));
+    ///
+    /// // Writer options change the written file and with it the hash
+    /// assert_ne!(plain, hash(DefinitionFileGenerator::new(definitions()).header("Generated")));
+    /// ```
+    pub fn content_hash(&self) -> GenResult<u64> {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer)?;
+
+        let mut hasher = StableHasher::default();
+        hasher.write(&buffer);
+        Ok(hasher.finish())
+    }
+
     /// PERF: Check if there is a good api for adding color when printing to stdout, stderr, etc
     ///
     /// Write the full definition group to the specified `io`
-    pub fn write<W: std::io::Write>(&self, mut buffer: W) -> mlua::Result<()> {
-        writeln!(buffer, "--- @meta\n")?;
+    pub fn write<W: std::io::Write>(&self, buffer: W) -> GenResult<()> {
+        self.write_collect(buffer).map(|_| ())
+    }
 
-        for definition in self.definition.iter() {
-            match &definition.ty {
-                Type::Value(ty) => {
-                    if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+    /// Write the full definition group to the specified `io` returning the error of each entry
+    /// that was skipped
+    ///
+    /// When the generator is [`strict`][DefinitionFileGenerator::strict] the first invalid entry
+    /// returns an error instead. I/O errors are always returned.
+    pub fn write_collect<W: std::io::Write>(&self, mut buffer: W) -> Result<Vec<SkippedEntry>, GenError> {
+        self.write_header(&mut buffer)?;
+
+        let mut errors = Vec::new();
+        for definition in self.entries() {
+            #[cfg(feature = "log")]
+            log::debug!("writing definition entry '{}'", definition.name);
+
+            // Entries are rendered separately so an invalid entry doesn't leave partial output
+            let mut entry = Vec::new();
+            match self.write_entry(&mut entry, definition) {
+                Ok(()) => {
+                    buffer.write_all(&entry)?;
+                    writeln!(buffer)?;
+                }
+                Err(error) if !self.options.strict => {
+                    #[cfg(feature = "log")]
+                    log::warn!("skipping definition entry '{}': {error}", definition.name);
+
+                    errors.push(SkippedEntry {
+                        name: definition.name.to_string(),
+                        error,
+                    })
+                }
+                Err(error) => return Err(Self::entry_error(&definition.name, error)),
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// Wrap an error with the entry that was being written, i.e.
+    /// `while writing 'Example': invalid root level type: Class`
+    fn entry_error(name: &str, error: GenError) -> GenError {
+        match error {
+            GenError::Io(error) => GenError::Io(error),
+            error => GenError::Entry {
+                name: name.to_string(),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    fn write_entry<W: std::io::Write>(&self, buffer: &mut W, definition: &Entry<'_>) -> GenResult<()> {
+        match &definition.ty {
+            Type::Value(ty) => {
+                if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+
+                writeln!(buffer, "--- @type {}", self.options.type_annotation(ty, "")?)?;
+                writeln!(buffer, "{} = nil", definition.name)?;
+            }
+            Type::Class(type_data) => {
+                let mut type_data = type_data.clone();
+                type_data.resolve_self(&Type::single(definition.name.to_string()));
+
+                if let Some(docs) =
+                    Self::accumulate_docs(&[definition.doc.as_deref(), type_data.type_doc.as_deref()])
+                {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+                if type_data.exact {
+                    writeln!(buffer, "--- @class (exact) {}", definition.name)?;
+                } else {
+                    writeln!(buffer, "--- @class {}", definition.name)?;
+                }
+
+                for (name, field) in type_data.static_fields.iter() {
+                    if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
                         writeln!(buffer, "{}", docs.join("\n"))?;
                     }
-
-                    writeln!(buffer, "--- @type {}", Self::type_signature(ty)?)?;
-                    writeln!(buffer, "{} = nil", definition.name)?;
+                    writeln!(
+                        buffer,
+                        "--- @field {}{name} {}",
+                        field.visibility.keyword().map(|v| format!("{v} ")).unwrap_or_default(),
+                        self.options.type_signature(&field.ty)?
+                    )?;
                 }
-                Type::Class(type_data) => {
-                    if let Some(docs) =
-                        Self::accumulate_docs(&[definition.doc.as_deref(), type_data.type_doc.as_deref()])
-                    {
+
+                for (name, field) in type_data.fields.iter() {
+                    if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
                         writeln!(buffer, "{}", docs.join("\n"))?;
                     }
-                    writeln!(buffer, "--- @class {}", definition.name)?;
+                    writeln!(
+                        buffer,
+                        "--- @field {}{name} {}",
+                        field.visibility.keyword().map(|v| format!("{v} ")).unwrap_or_default(),
+                        self.options.type_signature(&field.ty)?
+                    )?;
+                }
+
+                let class_functions = match definition.function_style {
+                    FunctionStyle::Type => ClassFunctionStyle::Field,
+                    FunctionStyle::Stub => self.options.class_functions,
+                };
+                if class_functions != ClassFunctionStyle::Table {
+                    for (name, func) in type_data.functions.iter() {
+                        self.write_function_field(buffer, name, None, func)?;
+                    }
+                    for (name, func) in type_data.methods.iter() {
+                        self.write_function_field(buffer, name, Some(&definition.name), func)?;
+                    }
+                }
+                let (functions, methods) = if class_functions == ClassFunctionStyle::Field {
+                    (Vec::new(), Vec::new())
+                } else {
+                    (type_data.functions.iter().collect::<Vec<_>>(), type_data.methods.iter().collect::<Vec<_>>())
+                };
 
-                    for (name, field) in type_data.static_fields.iter() {
-                        if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
+                // Metamethods that LuaLS understands natively are rendered as annotations and
+                // kept out of the generated `__metatable` block
+                for (name, func) in type_data.meta_functions.iter().chain(type_data.meta_methods.iter()) {
+                    if name == "__len" {
+                        if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
                             writeln!(buffer, "{}", docs.join("\n"))?;
                         }
+                        let ret = func
+                            .returns
+                            .first()
+                            .map(|r| self.options.type_signature(&r.ty))
+                            .transpose()?
+                            .unwrap_or_else(|| self.options.number_type("integer").into());
+                        writeln!(buffer, "--- @operator len: {ret}")?;
+                    }
+                }
+
+                let meta_functions = type_data
+                    .meta_functions
+                    .iter()
+                    .filter(|(name, _)| !Self::is_annotated_meta(name))
+                    .collect::<Vec<_>>();
+                let meta_methods = type_data
+                    .meta_methods
+                    .iter()
+                    .filter(|(name, _)| !Self::is_annotated_meta(name))
+                    .collect::<Vec<_>>();
+
+                if !functions.is_empty()
+                    || !methods.is_empty()
+                    || !type_data.meta_fields.is_empty()
+                    || !meta_functions.is_empty()
+                    || !meta_methods.is_empty()
+                {
+                    let single = self.options.indent.clone();
+                    let double = self.options.indent.repeat(2);
+                    writeln!(buffer, "local _Class_{} = {{", definition.name)?;
+                    for (name, func) in functions.iter() {
+                        if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
+                            writeln!(buffer, "{single}{}", docs.join(&format!("\n{single}")))?;
+                        }
                         writeln!(
                             buffer,
-                            "--- @field {name} {}",
-                            Self::type_signature(&field.ty)?
+                            "{single}{},",
+                            self.function_signature(name.to_string(), func, true)?
+                            .join(&format!("\n{single}"))
                         )?;
                     }
 
-                    for (name, field) in type_data.fields.iter() {
-                        if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
-                            writeln!(buffer, "{}", docs.join("\n"))?;
+                    for (name, func) in methods.iter() {
+                        if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
+                            writeln!(buffer, "{single}{}", docs.join(&format!("\n{single}")))?;
                         }
                         writeln!(
                             buffer,
-                            "--- @field {name} {}",
-                            Self::type_signature(&field.ty)?
+                            "{single}{},",
+                            self.method_signature(name.to_string(), definition.name.to_string(), func, true)?
+                            .join(&format!("\n{single}"))
                         )?;
                     }
 
-                    if !type_data.functions.is_empty()
-                        || !type_data.methods.is_empty()
-                        || !type_data.meta_fields.is_empty()
-                        || !type_data.meta_fields.is_empty()
-                        || !type_data.meta_functions.is_empty()
-                        || !type_data.meta_methods.is_empty()
+                    if !type_data.meta_fields.is_empty()
+                        || !meta_functions.is_empty()
+                        || !meta_methods.is_empty()
                     {
-                        writeln!(buffer, "local _Class_{} = {{", definition.name)?;
-                        for (name, func) in type_data.functions.iter() {
+                        writeln!(buffer, "{single}__metatable = {{")?;
+                        for (name, field) in type_data.meta_fields.iter() {
+                            if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
+                                writeln!(buffer, "{double}{}", docs.join(&format!("\n{double}")))?;
+                            }
+                            writeln!(buffer, "{double}--- @type {}", self.options.type_annotation(&field.ty, &double)?)?;
+                            writeln!(buffer, "{double}{name} = nil,")?;
+                        }
+
+                        for (name, func) in meta_functions.iter() {
                             if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
-                                writeln!(buffer, "  {}", docs.join("\n  "))?;
+                                writeln!(buffer, "{double}{}", docs.join(&format!("\n{double}")))?;
                             }
                             writeln!(
                                 buffer,
-                                "  {},",
-                                Self::function_signature(
-                                    name.to_string(),
-                                    &func.params,
-                                    &func.returns,
-                                    true
-                                )?
-                                .join("\n  ")
+                                "{double}{},",
+                                self.function_signature(name.to_string(), func, true)?
+                                .join(&format!("\n{double}"))
                             )?;
                         }
 
-                        for (name, func) in type_data.methods.iter() {
+                        for (name, func) in meta_methods.iter() {
                             if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
-                                writeln!(buffer, "  {}", docs.join("\n  "))?;
+                                writeln!(buffer, "{double}{}", docs.join(&format!("\n{double}")))?;
                             }
                             writeln!(
                                 buffer,
-                                "  {},",
-                                Self::method_signature(
-                                    name.to_string(),
-                                    definition.name.to_string(),
-                                    &func.params,
-                                    &func.returns,
-                                    true
-                                )?
-                                .join("\n  ")
+                                "{double}{},",
+                                self.method_signature(name.to_string(), definition.name.to_string(), func, true)?
+                                .join(&format!("\n{double}"))
                             )?;
                         }
-
-                        if !type_data.meta_fields.is_empty()
-                            || !type_data.meta_functions.is_empty()
-                            || !type_data.meta_methods.is_empty()
-                        {
-                            writeln!(buffer, "  __metatable = {{")?;
-                            for (name, field) in type_data.meta_fields.iter() {
-                                if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
-                                    writeln!(buffer, "    {}", docs.join("\n    "))?;
-                                }
-                                writeln!(buffer, "--- @type {}", Self::type_signature(&field.ty)?)?;
-                                writeln!(buffer, "{name} = nil,")?;
-                            }
-
-                            for (name, func) in type_data.meta_functions.iter() {
-                                if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
-                                    writeln!(buffer, "    {}", docs.join("\n    "))?;
-                                }
-                                writeln!(
-                                    buffer,
-                                    "    {},",
-                                    Self::function_signature(
-                                        name.to_string(),
-                                        &func.params,
-                                        &func.returns,
-                                        true
-                                    )?
-                                    .join("\n    ")
-                                )?;
-                            }
-
-                            for (name, func) in type_data.meta_methods.iter() {
-                                if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
-                                    writeln!(buffer, "    {}", docs.join("\n    "))?;
-                                }
-                                writeln!(
-                                    buffer,
-                                    "    {},",
-                                    Self::method_signature(
-                                        name.to_string(),
-                                        definition.name.to_string(),
-                                        &func.params,
-                                        &func.returns,
-                                        true
-                                    )?
-                                    .join("\n    ")
-                                )?;
-                            }
-                            writeln!(buffer, "  }}")?;
-                        }
-
-                        writeln!(buffer, "}}")?;
+                        writeln!(buffer, "{single}}}")?;
                     }
+
+                    writeln!(buffer, "}}")?;
                 }
-                Type::Enum(name, types) => {
-                    if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
-                        writeln!(buffer, "{}", docs.join("\n"))?;
-                    }
-                    writeln!(
+            }
+            Type::Enum(name, types) => {
+                if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+                writeln!(
+                    buffer,
+                    "--- @alias {name} {}",
+                    types
+                        .iter()
+                        .map(|ty| self.options.type_signature(ty))
+                        .collect::<GenResult<Vec<_>>>()?
+                        .join("\n---  | ")
+                )?;
+            }
+            Type::Alias(ty) => {
+                if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+                match ty.clone().simplify() {
+                    // LuaLS has no intersection syntax so it lowers to a class inheriting each member
+                    Type::Intersection(types) => writeln!(
                         buffer,
-                        "--- @alias {name} {}",
+                        "--- @class {}: {}",
+                        definition.name,
                         types
                             .iter()
-                            .map(Self::type_signature)
-                            .collect::<mlua::Result<Vec<_>>>()?
+                            .map(|ty| self.options.type_signature(ty))
+                            .collect::<GenResult<Vec<_>>>()?
+                            .join(", ")
+                    )?,
+                    Type::Union(types) if types.len() > self.options.alias_union_threshold => writeln!(
+                        buffer,
+                        "--- @alias {} {}",
+                        definition.name,
+                        types
+                            .iter()
+                            .map(|ty| self.options.type_signature(ty))
+                            .collect::<GenResult<Vec<_>>>()?
                             .join("\n---  | ")
-                    )?;
-                }
-                Type::Alias(ty) => {
-                    if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
-                        writeln!(buffer, "{}", docs.join("\n"))?;
-                    }
-                    writeln!(
+                    )?,
+                    ty => writeln!(
                         buffer,
                         "--- @alias {} {}",
                         definition.name,
-                        Self::type_signature(ty)?
-                    )?;
+                        self.options.type_signature(&ty)?
+                    )?,
                 }
-                Type::Function { params, returns } => {
-                    if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
-                        writeln!(buffer, "{}", docs.join("\n"))?;
-                    }
-                    writeln!(
-                        buffer,
-                        "{}",
-                        Self::function_signature(
-                            definition.name.to_string(),
-                            params,
-                            returns,
-                            false
-                        )?
-                        .join("\n")
-                    )?;
+            }
+            Type::Function { params, returns, overloads, usage } if definition.function_style == FunctionStyle::Type => {
+                if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+                for line in Self::usage_lines(usage) {
+                    writeln!(buffer, "{line}")?;
                 }
-                Type::Module(module) => {
-                    if let Some(docs) =
-                        Self::accumulate_docs(&[definition.doc.as_deref(), module.doc.as_deref()])
-                    {
-                        writeln!(buffer, "{}", docs.join("\n"))?;
-                    }
 
-                    write!(buffer, "{} = ", definition.name)?;
-                    let mut path = Vec::new();
-                    Self::write_module(&mut buffer, module, &mut path)?;
-                    writeln!(buffer)?;
-                },
-                other => {
-                    return Err(mlua::Error::runtime(format!(
-                        "invalid root level type: {}",
-                        other.as_ref()
-                    )))
+                let signatures = std::iter::once((params, returns))
+                    .chain(overloads.iter().map(|(params, returns)| (params, returns)))
+                    .map(|(params, returns)| self.options.fun_signature(None, params, returns))
+                    .collect::<GenResult<Vec<_>>>()?;
+                writeln!(buffer, "--- @type {}", Self::signature_union(signatures))?;
+                writeln!(buffer, "{} = nil", definition.name)?;
+            }
+            Type::Function { params, returns, overloads, usage } => {
+                if let Some(docs) = Self::accumulate_docs(&[definition.doc.as_deref()]) {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
                 }
+                writeln!(
+                    buffer,
+                    "{}",
+                    self.function_signature(
+                        definition.name.to_string(),
+                        &Func {
+                            params: params.clone(),
+                            returns: returns.clone(),
+                            overloads: overloads.clone(),
+                            usage: usage.clone(),
+                            is_async: false,
+                            doc: None,
+                        },
+                        false
+                    )?
+                    .join("\n")
+                )?;
             }
-            writeln!(buffer)?;
+            Type::Module(module) => {
+                if let Some(docs) =
+                    Self::accumulate_docs(&[definition.doc.as_deref(), module.doc.as_deref()])
+                {
+                    writeln!(buffer, "{}", docs.join("\n"))?;
+                }
+
+                // Nested modules are plain tables while the root module is declared as a class
+                let mut module = module.clone();
+                for nested in module.nested_modules.values_mut() {
+                    nested.resolve_self(&Type::single("table"));
+                }
+                module.resolve_self(&Type::single(definition.name.to_string()));
+
+                writeln!(buffer, "--- @class {}", definition.name)?;
+                write!(buffer, "{} = ", definition.name)?;
+                let mut path = Vec::new();
+                self.write_module(buffer, &module, &definition.name, &mut path)?;
+                writeln!(buffer)?;
+            },
+            other => return Err(GenError::InvalidRootType(other.as_ref().to_string())),
         }
 
         Ok(())
     }
 
-    fn function_signature(
-        name: String,
-        params: &[Param],
-        returns: &[Return],
-        assign: bool,
-    ) -> mlua::Result<Vec<String>> {
-        let mut result = Vec::new();
+    fn write_header<W: std::io::Write>(&self, buffer: &mut W) -> GenResult<()> {
+        let mut lines = Vec::new();
+        if self.options.meta {
+            match self.definition.meta.as_deref() {
+                Some(name) => lines.push(format!("--- @meta {name}")),
+                None => lines.push("--- @meta".to_string()),
+            }
+        }
+
+        let mut header = Self::accumulate_docs(&[self.options.header.as_deref()]).unwrap_or_default();
+        header.extend(self.options.stamp.lines());
+        if self.options.header_after_meta || header.is_empty() {
+            lines.extend(header);
+        } else {
+            lines.splice(0..0, header);
+        }
+
+        if !lines.is_empty() {
+            writeln!(buffer, "{}\n", lines.join("\n"))?;
+        }
+        Ok(())
+    }
+
+    fn function_signature(&self, name: String, func: &Func, assign: bool) -> GenResult<Vec<String>> {
+        let Func { params, returns, overloads, usage, is_async, .. } = func;
+        let mut result = Self::usage_lines(usage);
+        if *is_async {
+            result.push("--- @async".into());
+        }
+
+        for (params, returns) in overloads.iter() {
+            result.push(self.overload_signature(None, params, returns)?);
+        }
 
         for (i, param) in params.iter().enumerate() {
-            let doc = param.doc.as_deref().unwrap_or_default();
-            result.push(match param.name.as_deref() {
-                Some(name) => format!("--- @param {name} {} {doc}", Self::type_signature(&param.ty)?),
-                None => format!("--- @param param{i} {} {doc}", Self::type_signature(&param.ty)?),
-            });
+            result.push(format!(
+                "--- @param {} {} {}",
+                param.display_name(i, &self.options.param_naming),
+                self.options.param_signature(param)?,
+                param.display_doc()
+            ));
         }
 
-        for ret in returns.iter() {
-            let doc = ret.doc.as_deref().unwrap_or_default();
-            result.push(format!("--- @return {} {doc}", Self::type_signature(&ret.ty)?));
+        for ret in WriterOptions::void_returns(returns).iter() {
+            result.push(self.options.return_annotation(ret)?);
         }
 
         result.push(format!(
@@ -342,36 +1442,107 @@ impl DefinitionWriter<'_> {
             params
                 .iter()
                 .enumerate()
-                .map(|(i, v)| v
-                    .name
-                    .as_ref()
-                    .map(|v| v.to_string())
-                    .unwrap_or(format!("param{i}")))
+                .map(|(i, v)| self.options.param_naming.name_of(v, i))
                 .collect::<Vec<_>>()
                 .join(", "),
         ));
         Ok(result)
     }
 
+    /// Render each usage example as its own `--- @usage` block
+    fn usage_lines(usage: &[Cow<'static, str>]) -> Vec<String> {
+        usage
+            .iter()
+            .flat_map(|example| {
+                example.split('\n').enumerate().map(|(i, line)| {
+                    if i == 0 {
+                        format!("--- @usage {line}")
+                    } else {
+                        format!("--- {line}")
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Write a class function or method as a `--- @field name fun(...)` entry. Overloads are
+    /// written as additional members of the field's type
+    fn write_function_field<W: std::io::Write>(
+        &self,
+        buffer: &mut W,
+        name: &str,
+        class: Option<&str>,
+        func: &Func,
+    ) -> GenResult<()> {
+        if let Some(docs) = Self::accumulate_docs(&[func.doc.as_deref()]) {
+            writeln!(buffer, "{}", docs.join("\n"))?;
+        }
+
+        for line in Self::usage_lines(&func.usage) {
+            writeln!(buffer, "{line}")?;
+        }
+        if func.is_async {
+            writeln!(buffer, "--- @async")?;
+        }
+
+        let mut signatures = Vec::from([self.options.fun_signature(class, &func.params, &func.returns)?]);
+        for (params, returns) in func.overloads.iter() {
+            signatures.push(self.options.fun_signature(class, params, returns)?);
+        }
+        writeln!(buffer, "--- @field {name} {}", Self::signature_union(signatures))?;
+        Ok(())
+    }
+
+    /// Join function signatures into a single type. Multiple signatures are parenthesized so the
+    /// union isn't parsed as part of a signature's return type
+    fn signature_union(signatures: Vec<String>) -> String {
+        match signatures.len() {
+            1 => signatures.join(""),
+            _ => signatures.iter().map(|v| format!("({v})")).collect::<Vec<_>>().join(" | "),
+        }
+    }
+
+    /// Render an additional function signature as `--- @overload fun(...)`, prefixing a `self`
+    /// param when rendering for a method
+    fn overload_signature(
+        &self,
+        class: Option<&str>,
+        params: &[Param],
+        returns: &[Return],
+    ) -> GenResult<String> {
+        Ok(format!("--- @overload {}", self.options.fun_signature(class, params, returns)?))
+    }
+
+    /// Render a function type with typed params, i.e. `fun(self: Class, name: string): boolean`,
+    /// prefixing a `self` param when rendering for a method
     fn method_signature(
+        &self,
         name: String,
         class: String,
-        params: &[Param],
-        returns: &[Return],
+        func: &Func,
         assign: bool,
-    ) -> mlua::Result<Vec<String>> {
-        let mut result = Vec::from([format!("--- @param self {class}")]);
+    ) -> GenResult<Vec<String>> {
+        let Func { params, returns, overloads, usage, is_async, .. } = func;
+        let mut result = Self::usage_lines(usage);
+        if *is_async {
+            result.push("--- @async".into());
+        }
+        for (params, returns) in overloads.iter() {
+            result.push(self.overload_signature(Some(&class), params, returns)?);
+        }
+
+        result.push(format!("--- @param self {class}"));
         for (i, param) in params.iter().enumerate() {
-            let doc = param.doc.as_deref().unwrap_or_default();
-            result.push(match param.name.as_deref() {
-                Some(name) => format!("--- @param {name} {} {doc}", Self::type_signature(&param.ty)?),
-                None => format!("--- @param param{i} {} {doc}", Self::type_signature(&param.ty)?),
-            });
+            result.push(format!(
+                "--- @param {} {} {}",
+                param.display_name(i, &self.options.param_naming),
+                self.options.param_signature(param)?,
+                param.display_doc()
+            ));
         }
 
-        for ret in returns.iter() {
-            let doc = ret.doc.as_deref().unwrap_or_default();
-            result.push(format!("--- @return {} {doc}", Self::type_signature(&ret.ty)?));
+        for ret in WriterOptions::void_returns(returns).iter() {
+            result.push(self.options.return_annotation(ret)?);
         }
 
         result.push(format!(
@@ -390,110 +1561,43 @@ impl DefinitionWriter<'_> {
             params
                 .iter()
                 .enumerate()
-                .map(|(i, v)| v
-                    .name
-                    .as_ref()
-                    .map(|v| v.to_string())
-                    .unwrap_or(format!("param{i}")))
+                .map(|(i, v)| self.options.param_naming.name_of(v, i))
                 .collect::<Vec<_>>()
                 .join(", "),
         ));
         Ok(result)
     }
 
-    fn type_signature(ty: &Type) -> mlua::Result<String> {
-        Ok(match ty {
-            Type::Enum(name, _) => name.to_string(),
-            Type::Single(value) => value.to_string(),
-            Type::Tuple(types) => {
-                format!(
-                    "{{ {} }}",
-                    types
-                        .iter()
-                        .enumerate()
-                        .map(|(i, t)| Ok(format!("[{}]: {}", i + 1, Self::type_signature(t)?)))
-                        .collect::<mlua::Result<Vec<_>>>()?
-                        .join(", ")
-                )
-            }
-            Type::Variadic(ty) => {
-                format!("...{}", Self::type_signature(ty)?)
-            }
-            Type::Array(ty) => {
-                format!("{{ [integer]: {} }}", Self::type_signature(ty)?)
-            }
-            Type::Map(key, value) => {
-                format!(
-                    "{{ [{}]: {} }}",
-                    Self::type_signature(key)?,
-                    Self::type_signature(value)?
-                )
-            }
-            Type::Function { params, returns } => {
-                format!(
-                    "fun({}){}",
-                    params
-                        .iter()
-                        .enumerate()
-                        .map(|(i, v)| {
-                            v.name
-                                .as_ref()
-                                .map(|v| v.to_string())
-                                .unwrap_or(format!("param{i}"))
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                    if returns.is_empty() {
-                        String::new()
-                    } else {
-                        format!(
-                            ": {}",
-                            returns
-                                .iter()
-                                .map(|v| Self::type_signature(&v.ty))
-                                .collect::<mlua::Result<Vec<_>>>()?
-                                .join(", ")
-                        )
-                    }
-                )
-            }
-            Type::Union(types) => types
-                .iter()
-                .map(Self::type_signature)
-                .collect::<mlua::Result<Vec<_>>>()?
-                .join(" | "),
-            Type::Struct(entries) => {
-                format!(
-                    "{{ {} }}",
-                    entries
-                        .iter()
-                        .map(|(k, v)| { Ok(format!("{k}: {}", Self::type_signature(v)?)) })
-                        .collect::<mlua::Result<Vec<_>>>()?
-                        .join(", ")
-                )
-            }
-            other => {
-                return Err(mlua::Error::runtime(format!(
-                    "type cannot be a type signature: {}",
-                    other.as_ref()
-                )))
-            }
-        })
+    /// Metamethods that LuaLS either understands natively (`__tostring`, `__pairs`, `__ipairs`)
+    /// or that are rendered as an `@operator` annotation (`__len`) instead of a metatable member
+    fn is_annotated_meta(name: &str) -> bool {
+        matches!(name, "__tostring" | "__len" | "__pairs" | "__ipairs")
     }
 
+    /// Doc comment lines for each doc, separating each doc into its own paragraph with a blank
+    /// `---` line
     fn accumulate_docs(docs: &[Option<&str>]) -> Option<Vec<String>> {
         let docs = docs.iter().filter_map(|v| *v).collect::<Vec<_>>();
-        (!docs.is_empty()).then_some({
+        (!docs.is_empty()).then(|| {
             docs.iter()
-                .flat_map(|v| v.split('\n').map(|v| format!("--- {v}")))
+                .enumerate()
+                .flat_map(|(i, doc)| {
+                    (i > 0)
+                        .then(|| "---".to_string())
+                        .into_iter()
+                        .chain(doc.split('\n').map(|line| match line.trim_end() {
+                            "" => "---".to_string(),
+                            line => format!("--- {line}"),
+                        }))
+                })
                 .collect::<Vec<_>>()
         })
     }
 
-    fn write_module<B: std::io::Write>(buffer: &mut B, module: &TypedModuleBuilder, path: &mut Vec<String>) -> mlua::Result<()> {
-        let indent = path.len()*2;
-        let current_offset = (0..indent).map(|_| ' ').collect::<String>();
-        let single_offset = (0..indent+2).map(|_| ' ').collect::<String>();
+    /// Write a module as a table literal where methods take `self: {class}`
+    fn write_module<B: std::io::Write>(&self, buffer: &mut B, module: &TypedModuleBuilder, class: &str, path: &mut Vec<String>) -> GenResult<()> {
+        let current_offset = self.options.indent.repeat(path.len());
+        let single_offset = self.options.indent.repeat(path.len() + 1);
 
         if module.is_empty() {
             write!(buffer, "{{}}")?;
@@ -511,12 +1615,12 @@ impl DefinitionWriter<'_> {
                 &Type::Module(ref module) => {
                     write!(buffer, "{single_offset}{name} = ")?;
                     path.push(name.to_string());
-                    Self::write_module(buffer, module, path)?;
+                    self.write_module(buffer, module, "table", path)?;
                     path.pop();
                     writeln!(buffer, ",")?;
                 },
                 other => {
-                    writeln!(buffer, "{single_offset}--- @type {}", Self::type_signature(other)?)?;
+                    writeln!(buffer, "{single_offset}--- @type {}", self.options.type_annotation(other, &single_offset)?)?;
                     writeln!(buffer, "{single_offset}{name} = nil,", )?
                 },
             }
@@ -529,7 +1633,7 @@ impl DefinitionWriter<'_> {
 
             write!(buffer, "{single_offset}{name} = ")?;
             path.push(name.to_string());
-            Self::write_module(buffer, nested, path)?;
+            self.write_module(buffer, nested, "table", path)?;
             path.pop();
             writeln!(buffer, ",")?;
         }
@@ -539,7 +1643,7 @@ impl DefinitionWriter<'_> {
                 writeln!(buffer, "{single_offset}{}", docs.join(format!("\n{single_offset}").as_str()))?;
             }
 
-            writeln!(buffer, "{single_offset}{},", Self::function_signature(name.to_string(), &func.params, &func.returns, true)?.join(format!("\n{single_offset}").as_str()))?;
+            writeln!(buffer, "{single_offset}{},", self.function_signature(name.to_string(), func, true)?.join(format!("\n{single_offset}").as_str()))?;
         }
 
         for (name, func) in module.methods.iter() {
@@ -547,29 +1651,31 @@ impl DefinitionWriter<'_> {
                 writeln!(buffer, "{single_offset}{}", docs.join(format!("\n{single_offset}").as_str()))?;
             }
 
-            writeln!(buffer, "{single_offset}{},", Self::method_signature(name.to_string(), "table".into(), &func.params, &func.returns, true)?.join(format!("\n{single_offset}").as_str()))?;
+            writeln!(buffer, "{single_offset}{},", self.method_signature(name.to_string(), class.to_string(), func, true)?.join(format!("\n{single_offset}").as_str()))?;
         }
 
         if !module.is_meta_empty() {
             writeln!(buffer, "{single_offset}__metatable = {{")?;
 
-            let double_offset = (0..indent+4).map(|_| ' ').collect::<String>();
+            // Nested modules are written relative to `path`, so the metatable counts as a level
+            path.push("__metatable".to_string());
+            let double_offset = self.options.indent.repeat(path.len() + 1);
 
             for (name, field) in module.meta_fields.iter() {
                 if let Some(docs) = Self::accumulate_docs(&[field.doc.as_deref()]) {
-                    writeln!(buffer, "{double_offset}{}", docs.join(format!("\n{single_offset}").as_str()))?;
+                    writeln!(buffer, "{double_offset}{}", docs.join(format!("\n{double_offset}").as_str()))?;
                 }
 
                 match &field.ty {
                     &Type::Module(ref module) => {
                         write!(buffer, "{double_offset}{name} = ")?;
                         path.push(name.to_string());
-                        Self::write_module(buffer, module, path)?;
+                        self.write_module(buffer, module, "table", path)?;
                         path.pop();
                         writeln!(buffer, ",")?;
                     },
                     other => {
-                        writeln!(buffer, "{double_offset}--- @type {}", Self::type_signature(other)?)?;
+                        writeln!(buffer, "{double_offset}--- @type {}", self.options.type_annotation(other, &double_offset)?)?;
                         writeln!(buffer, "{double_offset}{name} = nil,", )?
                     },
                 }
@@ -580,7 +1686,7 @@ impl DefinitionWriter<'_> {
                     writeln!(buffer, "{double_offset}{}", docs.join(format!("\n{double_offset}").as_str()))?;
                 }
 
-                writeln!(buffer, "{double_offset}{},", Self::function_signature(name.to_string(), &func.params, &func.returns, true)?.join(format!("\n{double_offset}").as_str()))?;
+                writeln!(buffer, "{double_offset}{},", self.function_signature(name.to_string(), func, true)?.join(format!("\n{double_offset}").as_str()))?;
             }
 
             for (name, func) in module.meta_methods.iter() {
@@ -588,9 +1694,10 @@ impl DefinitionWriter<'_> {
                     writeln!(buffer, "{double_offset}{}", docs.join(format!("\n{double_offset}").as_str()))?;
                 }
 
-                writeln!(buffer, "{double_offset}{},", Self::method_signature(name.to_string(), "table".into(), &func.params, &func.returns, true)?.join(format!("\n{double_offset}").as_str()))?;
+                writeln!(buffer, "{double_offset}{},", self.method_signature(name.to_string(), class.to_string(), func, true)?.join(format!("\n{double_offset}").as_str()))?;
             }
 
+            path.pop();
             writeln!(buffer, "{single_offset}}},")?;
         }
 
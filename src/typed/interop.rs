@@ -0,0 +1,20 @@
+//! [`Typed`] implementations for common third party types, each behind a feature of the same name
+
+use super::{Type, Typed};
+
+/// `--- @alias DateTime string|number`
+///
+/// Register the alias with [`register_enum`][crate::typed::generator::DefinitionBuilder::register_enum]
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Typed for chrono::DateTime<Tz> {
+    fn ty() -> Type {
+        Type::Enum("DateTime".into(), vec![Type::single("string"), Type::single("number")])
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Typed for uuid::Uuid {
+    fn ty() -> Type {
+        Type::single("string")
+    }
+}
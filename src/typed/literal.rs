@@ -0,0 +1,56 @@
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+use super::{IntoLuaTypeLiteral, Type, Typed};
+
+/// Integer that is always `N`, typed as the integer literal `N`
+///
+/// Converts to and from lua as an `integer`, and converting from any other integer is an error.
+/// This allows modeling tagged unions that are keyed by an integer, i.e. a protocol message kind.
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{LiteralInt, Type, Typed};
+///
+/// type Ping = LiteralInt<1>;
+/// type Pong = LiteralInt<2>;
+///
+/// assert_eq!(Ping::ty(), Type::literal(1));
+/// assert_eq!(Option::<Pong>::ty(), Type::literal(2) | Type::single("nil"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LiteralInt<const N: i64>;
+
+impl<const N: i64> LiteralInt<N> {
+    /// The literal value
+    pub const VALUE: i64 = N;
+}
+
+impl<'lua, const N: i64> IntoLua<'lua> for LiteralInt<N> {
+    fn into_lua(self, lua: &'lua Lua) -> mlua::Result<Value<'lua>> {
+        N.into_lua(lua)
+    }
+}
+
+impl<'lua, const N: i64> FromLua<'lua> for LiteralInt<N> {
+    fn from_lua(value: Value<'lua>, lua: &'lua Lua) -> mlua::Result<Self> {
+        match i64::from_lua(value, lua)? {
+            value if value == N => Ok(Self),
+            value => Err(mlua::Error::runtime(format!(
+                "expected the integer literal {N} but got {value}"
+            ))),
+        }
+    }
+}
+
+impl<const N: i64> Typed for LiteralInt<N> {
+    fn ty() -> Type {
+        Type::literal(N)
+    }
+}
+
+impl<const N: i64> IntoLuaTypeLiteral for LiteralInt<N> {
+    fn to_type_literal(&self) -> Option<Type> {
+        Some(Type::literal(N))
+    }
+}
@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+
+use mlua::{FromLuaMulti, IntoLua, IntoLuaMulti, Lua, UserData};
+
+use crate::{extras::LuaExtras, MaybeSend};
+
+use super::{
+    generator::{short_type_name, Definition, DefinitionBuilder, Definitions, Entry},
+    Type, Typed, TypedModule, TypedMultiValue, TypedUserData,
+};
+
+/// Register globals in a [`Lua`] instance while recording their type information
+///
+/// Each method performs the runtime registration and adds the matching definition entry, so the
+/// runtime and the generated definitions come from the same description.
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::{mlua::{self, Lua}, typed::TypedLua};
+///
+/// let lua = Lua::new();
+/// let definitions = TypedLua::new(&lua)
+///     .global("version", 3)?
+///     .global_fn("greet", |_, name: String| Ok(format!("Hello, {name}!")))?
+///     .definitions();
+///
+/// assert_eq!(lua.load("return greet('World') .. version").eval::<String>()?, "Hello, World!3");
+///
+/// let init = definitions.get("init").unwrap();
+/// assert_eq!(init.iter().map(|v| v.name.as_ref()).collect::<Vec<_>>(), ["version", "greet"]);
+/// # Ok::<_, mlua::Error>(())
+/// ```
+pub struct TypedLua<'lua> {
+    lua: &'lua Lua,
+    definition: DefinitionBuilder<'static>,
+}
+
+impl<'lua> TypedLua<'lua> {
+    /// Start registering globals in `lua`
+    pub fn new(lua: &'lua Lua) -> Self {
+        Self {
+            lua,
+            definition: Definition::start(),
+        }
+    }
+
+    /// Set a global value
+    pub fn global<V>(mut self, name: impl Into<Cow<'static, str>>, value: V) -> mlua::Result<Self>
+    where
+        V: Typed + IntoLua<'lua>,
+    {
+        let name = name.into();
+        self.lua.set_global(name.as_ref(), value)?;
+        self.definition = self.definition.value::<V>(name);
+        Ok(self)
+    }
+
+    /// Set a global function created from a rust function
+    pub fn global_fn<Params, Response, F>(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        func: F,
+    ) -> mlua::Result<Self>
+    where
+        Params: TypedMultiValue + FromLuaMulti<'lua>,
+        Response: TypedMultiValue + IntoLuaMulti<'lua>,
+        F: Fn(&'lua Lua, Params) -> mlua::Result<Response> + MaybeSend + 'static,
+    {
+        let name = name.into();
+        self.lua.set_global(name.as_ref(), self.lua.create_function(func)?)?;
+        self.definition
+            .entries
+            .push(Entry::new(name, Type::function::<Params, Response>()));
+        Ok(self)
+    }
+
+    /// Build a module and set it as a global
+    pub fn module<M: TypedModule>(mut self, name: impl Into<Cow<'static, str>>) -> mlua::Result<Self> {
+        let name = name.into();
+        self.lua.set_global_module::<M>(name.as_ref())?;
+        self.definition = self.definition.try_register_module::<M>(name)?;
+        Ok(self)
+    }
+
+    /// Set a global to the userdata's proxy, which exposes its static functions and fields
+    ///
+    /// The userdata's class is registered along with the global
+    pub fn userdata<T>(mut self, name: impl Into<Cow<'static, str>>) -> mlua::Result<Self>
+    where
+        T: TypedUserData + UserData + 'static,
+    {
+        let name = name.into();
+        self.lua.set_global(name.as_ref(), self.lua.create_proxy::<T>()?)?;
+
+        let class = short_type_name::<T>();
+        self.definition = self.definition.register_class::<T>();
+        self.definition
+            .entries
+            .push(Entry::new(name, Type::Value(Box::new(Type::single(class)))));
+        Ok(self)
+    }
+
+    /// Definition group with every registered global
+    pub fn finish(self) -> Definition<'static> {
+        self.definition.finish()
+    }
+
+    /// Definitions with every registered global in a group named `init`
+    pub fn definitions(self) -> Definitions<'static> {
+        Definitions::start().define("init", self.finish()).finish()
+    }
+}
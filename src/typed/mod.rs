@@ -1,11 +1,19 @@
+mod bytes;
 mod function;
 pub mod generator;
+mod literal;
+mod lua;
 
 mod class;
 mod module;
+mod table;
+mod visitor;
+#[cfg(any(feature = "chrono", feature = "uuid"))]
+mod interop;
 
 pub use class::{
-    TypedClassBuilder, TypedDataFields, TypedDataMethods, TypedUserData, WrappedBuilder,
+    ClassDiff, Member, MemberKind, TypedClassBuilder, TypedDataDocumentation, TypedDataFields,
+    TypedDataMethods, TypedUserData, WrappedBuilder,
 };
 pub use module::{TypedModule, TypedModuleBuilder, TypedModuleFields, TypedModuleMethods};
 
@@ -14,8 +22,12 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
 };
 
-use function::Return;
-pub use function::{Param, TypedFunction};
+pub use bytes::LuaBytes;
+pub use function::{Param, Return, TypedFunction};
+pub use literal::LiteralInt;
+pub use lua::TypedLua;
+pub use table::TypedTable;
+pub use visitor::TypeVisitor;
 
 use mlua::Variadic;
 
@@ -30,8 +42,17 @@ pub trait Typed {
             doc: None,
             name: None,
             ty: Self::ty(),
+            optional: false,
+            default: None,
         }
     }
+
+    /// Whether the type represents no values at all when used as a [`TypedMultiValue`]
+    ///
+    /// Only `()` sets this so it is a single `nil` in a value context but an empty argument or
+    /// return list in a multi value context
+    #[doc(hidden)]
+    const IS_UNIT: bool = false;
 }
 
 macro_rules! impl_static_typed {
@@ -75,7 +96,7 @@ macro_rules! impl_static_typed_generic {
 impl_static_typed! {
     mlua::LightUserData => "lightuserdata",
     mlua::Error => "error",
-    String | &str => "string",
+    String | &str | Box<str> | std::rc::Rc<str> | std::sync::Arc<str> => "string",
     u8 | u16 | u32 | u64 | usize | u128 | i8 | i16 | i32 | i64 | isize | i128 => "integer",
     f32 | f64 => "number",
     bool => "boolean",
@@ -89,26 +110,60 @@ impl_static_typed_generic! {
     for<'lua> mlua::Thread<'lua> => "thread",
 }
 
-impl<T: Typed> Typed for Variadic<T> {
-    /// ...type
+/// `nil` when used as a single value, i.e. a field or a single return
+///
+/// As a [`TypedMultiValue`], i.e. a parameter or return list, `()` is no values at all
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{Type, Typed, TypedMultiValue};
+///
+/// assert_eq!(<()>::ty(), Type::single("nil"));
+/// assert!(<() as TypedMultiValue>::get_types().is_empty());
+/// ```
+impl Typed for () {
     fn ty() -> Type {
-        Type::Variadic(T::ty().into())
+        Type::single("nil")
     }
 
-    /// @param ... type
-    fn as_param() -> Param {
-        Param {
-            doc: None,
-            name: Some("...".into()),
-            ty: T::ty(),
-        }
+    const IS_UNIT: bool = true;
+}
+
+impl<T: Typed> Typed for Variadic<T> {
+    /// type...
+    ///
+    /// Written as `--- @param ... type` when used as a parameter and `--- @return type ...` when
+    /// used as a return
+    fn ty() -> Type {
+        Type::Variadic(T::ty().into())
     }
 }
 
 /// {type} | nil
+///
+/// A union type is flattened so the members sit next to `nil` instead of in a nested union
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{Type, Typed};
+///
+/// struct Color;
+/// impl Typed for Color {
+///     fn ty() -> Type {
+///         Type::literal_string("red") | Type::literal_string("green")
+///     }
+/// }
+///
+/// assert_eq!(
+///     Option::<Color>::ty(),
+///     Type::union([Type::literal_string("red"), Type::literal_string("green"), Type::single("nil")]),
+/// );
+/// ```
 impl<T: Typed> Typed for Option<T> {
     fn ty() -> Type {
-        Type::Union(vec![T::ty(), Type::Single("nil".into())])
+        T::ty() | Type::single("nil")
     }
 }
 
@@ -134,7 +189,7 @@ impl From<String> for Type {
 
 impl<I: Typed, const N: usize> Typed for [I; N] {
     fn ty() -> Type {
-        Type::Array(I::ty().into())
+        Type::array_of_len(I::ty(), N)
     }
 }
 impl<I: Typed> Typed for Vec<I> {
@@ -147,6 +202,43 @@ impl<I: Typed> Typed for &[I] {
         Type::Array(I::ty().into())
     }
 }
+
+/// Borrowed values are typed the same as the value, i.e. `&i32` is `integer`
+///
+/// `&str` and `&[I]` have their own impls since `str` and `[I]` aren't [`Typed`]
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{Type, Typed, TypedMultiValue};
+///
+/// assert_eq!(<&i32>::ty(), Type::single("integer"));
+/// assert_eq!(<(&String, &mut bool)>::get_types(), [Type::single("string"), Type::single("boolean")]);
+/// ```
+impl<T: Typed> Typed for &T {
+    fn ty() -> Type {
+        T::ty()
+    }
+
+    fn as_param() -> Param {
+        T::as_param()
+    }
+
+    const IS_UNIT: bool = T::IS_UNIT;
+}
+
+/// Borrowed values are typed the same as the value, i.e. `&mut i32` is `integer`
+impl<T: Typed> Typed for &mut T {
+    fn ty() -> Type {
+        T::ty()
+    }
+
+    fn as_param() -> Param {
+        T::as_param()
+    }
+
+    const IS_UNIT: bool = T::IS_UNIT;
+}
 impl<I: Typed> Typed for HashSet<I> {
     fn ty() -> Type {
         Type::Array(I::ty().into())
@@ -166,7 +258,7 @@ where
     V: Typed,
 {
     fn ty() -> Type {
-        Type::Map(K::ty().into(), V::ty().into())
+        Type::map(K::ty(), V::ty())
     }
 }
 impl<K, V> Typed for HashMap<K, V>
@@ -175,12 +267,12 @@ where
     V: Typed,
 {
     fn ty() -> Type {
-        Type::Map(K::ty().into(), V::ty().into())
+        Type::map(K::ty(), V::ty())
     }
 }
 
 /// Representation of a lua type for a rust type
-#[derive(Debug, Clone, PartialEq, strum::AsRefStr, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, PartialEq, strum::AsRefStr, PartialOrd, Eq, Ord, Hash)]
 pub enum Type {
     /// string
     /// nil
@@ -218,16 +310,37 @@ pub enum Type {
     Struct(BTreeMap<&'static str, Type>),
     Variadic(Box<Type>),
     Union(Vec<Type>),
+    /// A value that satisfies every member type
+    ///
+    /// LuaLS has no intersection syntax so this lowers to inheritance: an alias of an intersection
+    /// is written as `--- @class {name}: A, B`. It can not be used as an inline type signature.
+    Intersection(Vec<Type>),
     Array(Box<Type>),
+    /// Array with a known length
+    ///
+    /// Written as a tuple when the length is within the generator's tuple threshold, otherwise as an array
+    ArrayOfLen(Box<Type>, usize),
     Map(Box<Type>, Box<Type>),
+    /// Placeholder for the enclosing class or module, resolved when writing definitions
+    ///
+    /// Resolves to the class name inside a class, to the module name inside a root level module, and
+    /// to `table` inside a nested module
+    SelfType,
     Function {
         params: Vec<Param>,
         returns: Vec<Return>,
+        /// Additional signatures rendered as `--- @overload` when the function is a definition entry
+        overloads: Vec<(Vec<Param>, Vec<Return>)>,
+        /// Usage examples rendered as `--- @usage` when the function is a definition entry
+        usage: Vec<Cow<'static, str>>,
     },
 }
 
 /// Allows to union types
 ///
+/// Unions on either side are flattened so members are never duplicated, and boolean literals are
+/// collapsed, so `true | false` and `true | boolean` both become `boolean`
+///
 /// # Example
 ///
 /// ```
@@ -238,30 +351,62 @@ pub enum Type {
 impl std::ops::BitOr for Type {
     type Output = Self;
 
-    fn bitor(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Self::Union(mut types), Self::Union(other_types)) => {
-                for ty in other_types {
-                    if !types.contains(&ty) {
-                        types.push(ty);
-                    }
-                }
-                Self::Union(types)
-            }
-            (Self::Union(mut types), other) => {
-                if !types.contains(&other) {
-                    types.push(other)
-                }
-                Self::Union(types)
-            }
-            (current, other) => {
-                if current == other {
-                    current
-                } else {
-                    Self::Union(Vec::from([current, other]))
-                }
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self.union_with(rhs);
+        self
+    }
+}
+
+/// Union in place, same as [`Type::union_with`]
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::Type;
+///
+/// let mut ty = Type::single("string");
+/// ty |= Type::single("nil");
+/// assert_eq!(ty, Type::single("string") | Type::single("nil"));
+/// ```
+impl std::ops::BitOrAssign for Type {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.union_with(rhs);
+    }
+}
+
+/// Allows to intersect types
+///
+/// Intersections on either side are flattened so members are never duplicated
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::Type;
+///
+/// let value = Type::single("Iterator") & Type::single("Disposable");
+/// assert_eq!(value, Type::intersection([Type::single("Iterator"), Type::single("Disposable")]));
+/// ```
+impl std::ops::BitAnd for Type {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        let members = |ty: Type| match ty {
+            Self::Intersection(types) => types,
+            other => Vec::from([other]),
+        };
+
+        let mut types = Vec::new();
+        for ty in members(self).into_iter().chain(members(rhs)) {
+            if !types.contains(&ty) {
+                types.push(ty);
             }
         }
+
+        if types.len() == 1 {
+            types.pop().unwrap()
+        } else {
+            Self::Intersection(types)
+        }
     }
 }
 
@@ -276,6 +421,11 @@ impl Type {
         Self::Single(value.to_string().into())
     }
 
+    /// Create a lua type literal for a boolean. i.e. `true` or `false`
+    pub fn bool_literal(value: bool) -> Self {
+        Self::Single(if value { "true" } else { "false" }.into())
+    }
+
     /// Create a type that has a single value. i.e. `string`, `number`, etc...
     pub fn single(value: impl Into<Cow<'static, str>>) -> Self {
         Self::Single(value.into())
@@ -294,7 +444,20 @@ impl Type {
         Self::Alias(Box::new(ty))
     }
 
-    /// Create a type that is variadic. i.e. `...type`
+    /// Create a type that is variadic. i.e. `type...`
+    ///
+    /// Variadic params are written as `...: type` and variadic returns as `type...`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{mlua::Variadic, typed::{Type, Typed}};
+    ///
+    /// assert_eq!(Variadic::<String>::ty(), Type::variadic(Type::single("string")));
+    ///
+    /// let join = Type::function::<Variadic<String>, Variadic<String>>();
+    /// assert_eq!(join.render_pretty(2).unwrap(), "fun(...: string): string...");
+    /// ```
     pub fn variadic(ty: Type) -> Self {
         Self::Variadic(Box::new(ty))
     }
@@ -304,9 +467,76 @@ impl Type {
         Self::Array(Box::new(ty))
     }
 
+    /// Create a type that is an array with a known length. i.e. `{ [1]: type, [2]: type, [3]: type }`
+    pub fn array_of_len(ty: Type, len: usize) -> Self {
+        Self::ArrayOfLen(Box::new(ty), len)
+    }
+
+    /// Create a type that is a map. i.e. `{ [string]: type }`
+    ///
+    /// The key is simplified so a union of literals, or an enum of literals, is written as the
+    /// literal key set. i.e. `{ ["a"]: type, ["b"]: type }`
+    pub fn map(key: Type, value: Type) -> Self {
+        Self::Map(Box::new(key.simplify()), Box::new(value))
+    }
+
+    /// Get each member of the type if every one of them is a literal. i.e. `"a"`, `1`, or `true`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::Type;
+    ///
+    /// assert_eq!(Type::union([Type::literal(1), Type::literal(-2.5)]).literal_members(), Some(Vec::from(["1", "-2.5"])));
+    /// assert_eq!(Type::single("inf").literal_members(), None);
+    /// ```
+    pub fn literal_members(&self) -> Option<Vec<&str>> {
+        let members = match self {
+            Self::Union(types) | Self::Enum(_, types) => types.iter().collect::<Vec<_>>(),
+            Self::Single(_) => Vec::from([self]),
+            _ => return None,
+        };
+
+        members
+            .into_iter()
+            .map(|ty| match ty {
+                Self::Single(value)
+                    if value.starts_with('"')
+                        || value == "true"
+                        || value == "false"
+                        || Self::is_number_literal(value) =>
+                {
+                    Some(value.as_ref())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the value is a finite number literal, i.e. `1` or `-2.5`, but not `inf` or `nan`
+    fn is_number_literal(value: &str) -> bool {
+        value.strip_prefix('-').unwrap_or(value).starts_with(|c: char| c.is_ascii_digit())
+            && value.parse::<f64>().is_ok_and(f64::is_finite)
+    }
+
     /// Create a type that is a union. i.e. `string | integer | nil`
+    ///
+    /// Members that are unions themselves are flattened into the new union
     pub fn union(types: impl IntoIterator<Item = Type>) -> Self {
-        Self::Union(types.into_iter().collect())
+        Self::Union(
+            types
+                .into_iter()
+                .flat_map(|ty| match ty {
+                    Self::Union(types) => types,
+                    other => Vec::from([other]),
+                })
+                .collect(),
+        )
+    }
+
+    /// Create a type that is an intersection. i.e. `--- @class Name: A, B` when aliased
+    pub fn intersection(types: impl IntoIterator<Item = Type>) -> Self {
+        Self::Intersection(types.into_iter().collect())
     }
 
     /// create a type that is a tuple. i.e. `{ [1]: type, [2]: type }`
@@ -330,8 +560,434 @@ impl Type {
             params: Params::get_types_as_params(),
             returns: Response::get_types()
                 .into_iter()
-                .map(|ty| Return { doc: None, ty })
+                .map(|ty| Return { doc: None, name: None, ty })
+                .collect(),
+            overloads: Vec::new(),
+            usage: Vec::new(),
+        }
+    }
+
+    /// create a function type with named parameters for callbacks. i.e. `fun(ev: Event): boolean`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use mlua_extras::typed::{Type, Typed};
+    ///
+    /// let callback = Type::callback([("ev", Type::single("Event"))], [bool::ty()]);
+    ///
+    /// // A lone `nil` return is written without a return clause
+    /// let on_exit = Type::callback([("code", Type::single("integer"))], [Type::single("nil")]);
+    /// let fields = Type::Struct(BTreeMap::from([("on_exit", on_exit)]));
+    /// assert_eq!(fields.render_pretty(2).unwrap(), "{\n  on_exit: fun(code: integer),\n}");
+    /// ```
+    pub fn callback<P: Into<Param>>(
+        params: impl IntoIterator<Item = P>,
+        returns: impl IntoIterator<Item = Type>,
+    ) -> Self {
+        Self::Function {
+            params: params.into_iter().map(Into::into).collect(),
+            returns: returns
+                .into_iter()
+                .map(|ty| Return { doc: None, name: None, ty })
                 .collect(),
+            overloads: Vec::new(),
+            usage: Vec::new(),
+        }
+    }
+
+    /// Infer the type of a runtime lua value
+    ///
+    /// Functions are `fun()` since their signatures can't be recovered and userdata uses the name
+    /// from its metatable's `__name` (`__type` in luau), falling back to `userdata`. Tables are
+    /// inferred from all of their entries: an empty table is `table`, a table whose keys are
+    /// exactly `1..=n` is an array of its value types, and anything else is a map from its key
+    /// types to its value types. Tables that are already being inferred, i.e. cycles, are `table`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua::Lua;
+    /// use mlua_extras::typed::Type;
+    ///
+    /// let lua = Lua::new();
+    /// let value = lua.load("{ 1, 2, 'three' }").eval().unwrap();
+    /// assert_eq!(Type::from_value(&value), Type::array(Type::single("integer") | Type::single("string")));
+    /// ```
+    pub fn from_value(value: &mlua::Value<'_>) -> Self {
+        Self::from_value_visited(value, &mut HashSet::new())
+    }
+
+    fn from_value_visited(
+        value: &mlua::Value<'_>,
+        visited: &mut HashSet<*const std::ffi::c_void>,
+    ) -> Self {
+        match value {
+            mlua::Value::Nil => Self::single("nil"),
+            mlua::Value::Boolean(_) => Self::single("boolean"),
+            mlua::Value::Integer(_) => Self::single("integer"),
+            mlua::Value::Number(_) => Self::single("number"),
+            mlua::Value::String(_) => Self::single("string"),
+            mlua::Value::Function(_) => mlua::Function::ty(),
+            mlua::Value::UserData(data) => data
+                .get_metatable()
+                .and_then(|mt| mt.get::<String>(mlua::MetaMethod::Type))
+                .map(Self::single)
+                .unwrap_or_else(|_| Self::single("userdata")),
+            mlua::Value::Table(table) => {
+                if !visited.insert(table.to_pointer()) {
+                    return Self::single("table");
+                }
+
+                let mut keys: Option<Type> = None;
+                let mut values: Option<Type> = None;
+                let (mut count, mut max): (mlua::Integer, mlua::Integer) = (0, 0);
+                let mut sequence = true;
+                for (key, value) in table
+                    .clone()
+                    .pairs::<mlua::Value, mlua::Value>()
+                    .filter_map(Result::ok)
+                {
+                    count += 1;
+                    match key {
+                        mlua::Value::Integer(i) if i >= 1 => max = max.max(i),
+                        _ => sequence = false,
+                    }
+
+                    let key = Self::from_value_visited(&key, visited);
+                    let value = Self::from_value_visited(&value, visited);
+                    keys = Some(keys.map_or(key.clone(), |v| v | key));
+                    values = Some(values.map_or(value.clone(), |v| v | value));
+                }
+                visited.remove(&table.to_pointer());
+
+                // `n` distinct integer keys that are all in `1..=n` are exactly the sequence `1..=n`
+                match (keys, values) {
+                    (Some(_), Some(values)) if sequence && max == count => {
+                        Self::array(values)
+                    }
+                    (Some(keys), Some(values)) => Self::map(keys, values),
+                    _ => Self::single("table"),
+                }
+            }
+            other => Self::single(other.type_name()),
+        }
+    }
+
+    /// Render the type signature with table like types, i.e. structs, modules, and classes, broken
+    /// across multiple lines indented by `indent` spaces per level
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use mlua_extras::typed::Type;
+    ///
+    /// let ty = Type::Struct(BTreeMap::from([("name", Type::single("string"))]));
+    /// assert_eq!(ty.render_pretty(2).unwrap(), "{\n  name: string,\n}");
+    /// ```
+    pub fn render_pretty(&self, indent: usize) -> Result<String, generator::GenError> {
+        generator::WriterOptions::default().pretty_signature(self, indent)
+    }
+
+    /// Union `other` into this type in place without cloning either side
+    ///
+    /// Unions on either side are flattened so members are never duplicated, and boolean literals
+    /// are collapsed, so `true | false` and `true | boolean` both become `boolean`. Members are
+    /// compared with [`structurally_eq`][Type::structurally_eq], the same as
+    /// [`simplify`][Type::simplify]. This is what [`BitOr`][std::ops::BitOr] and
+    /// [`BitOrAssign`][std::ops::BitOrAssign] use.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::Type;
+    ///
+    /// let mut ty = Type::single("true");
+    /// ty.union_with(Type::single("string") | Type::single("false"));
+    /// assert_eq!(ty, Type::union([Type::single("boolean"), Type::single("string")]));
+    /// ```
+    pub fn union_with(&mut self, other: Type) {
+        let members = |ty: Type| match ty {
+            Self::Union(types) => types,
+            other => Vec::from([other]),
+        };
+
+        let mut types = Vec::new();
+        let lhs = std::mem::replace(self, Self::Union(Vec::new()));
+        for ty in members(lhs).into_iter().chain(members(other)) {
+            if !types.iter().any(|member: &Type| member.structurally_eq(&ty)) {
+                types.push(ty);
+            }
+        }
+
+        Self::collapse_booleans(&mut types);
+        *self = if types.len() == 1 {
+            types.pop().unwrap()
+        } else {
+            Self::Union(types)
+        };
+    }
+
+    /// Simplify the type by flattening nested unions, removing duplicate members, collapsing
+    /// boolean literals, and moving `nil` to the end of the union.
+    ///
+    /// A union that is left with a single member is replaced by that member. Nested types such as
+    /// arrays, maps, tuples, and function signatures are simplified recursively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::Type;
+    ///
+    /// let nested = Type::union([
+    ///     Type::union([Type::single("nil"), Type::single("string")]),
+    ///     Type::single("string"),
+    /// ]);
+    /// assert_eq!(
+    ///     nested.simplify(),
+    ///     Type::union([Type::single("string"), Type::single("nil")])
+    /// );
+    ///
+    /// let optional = Type::union([
+    ///     Type::union([Type::single("integer"), Type::single("nil")]),
+    ///     Type::single("integer"),
+    /// ]);
+    /// assert_eq!(
+    ///     Type::array(optional).simplify(),
+    ///     Type::array(Type::union([Type::single("integer"), Type::single("nil")]))
+    /// );
+    /// ```
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Union(types) => {
+                let mut members = Vec::new();
+                Self::flatten_union(types, &mut members);
+
+                Self::collapse_booleans(&mut members);
+                if let Some(index) = members.iter().position(|ty| matches!(ty, Type::Single(v) if v == "nil")) {
+                    let nil = members.remove(index);
+                    members.push(nil);
+                }
+
+                if members.len() == 1 {
+                    members.pop().unwrap()
+                } else {
+                    Self::Union(members)
+                }
+            }
+            Self::Intersection(types) => {
+                let mut members = Vec::new();
+                for ty in types {
+                    match ty.simplify() {
+                        Self::Intersection(nested) => members.extend(nested),
+                        other => members.push(other),
+                    }
+                }
+                let mut unique = Vec::new();
+                for ty in members {
+                    if !unique.contains(&ty) {
+                        unique.push(ty);
+                    }
+                }
+
+                if unique.len() == 1 {
+                    unique.pop().unwrap()
+                } else {
+                    Self::Intersection(unique)
+                }
+            }
+            Self::Value(ty) => Self::Value(Box::new(ty.simplify())),
+            Self::Alias(ty) => Self::Alias(Box::new(ty.simplify())),
+            Self::Enum(name, types) => {
+                let mut members = Vec::new();
+                Self::flatten_union(types, &mut members);
+                Self::Enum(name, members)
+            }
+            Self::Tuple(types) => Self::Tuple(types.into_iter().map(Self::simplify).collect()),
+            Self::Struct(entries) => Self::Struct(
+                entries
+                    .into_iter()
+                    .map(|(name, ty)| (name, ty.simplify()))
+                    .collect(),
+            ),
+            Self::Variadic(ty) => Self::Variadic(Box::new(ty.simplify())),
+            Self::Array(ty) => Self::Array(Box::new(ty.simplify())),
+            Self::ArrayOfLen(ty, len) => Self::ArrayOfLen(Box::new(ty.simplify()), len),
+            Self::Map(key, value) => Self::Map(Box::new(key.simplify()), Box::new(value.simplify())),
+            Self::Function { params, returns, overloads, usage } => Self::Function {
+                params: params
+                    .into_iter()
+                    .map(|mut param| {
+                        param.ty = param.ty.simplify();
+                        param
+                    })
+                    .collect(),
+                returns: returns
+                    .into_iter()
+                    .map(|mut ret| {
+                        ret.ty = ret.ty.simplify();
+                        ret
+                    })
+                    .collect(),
+                overloads,
+                usage,
+            },
+            other => other,
+        }
+    }
+
+    /// Replace every [`Type::SelfType`] placeholder with `ty`, recursing into nested types
+    ///
+    /// Classes and modules are not entered since they resolve their own `self`
+    pub fn resolve_self(&mut self, ty: &Type) {
+        match self {
+            Self::SelfType => *self = ty.clone(),
+            Self::Value(inner)
+            | Self::Alias(inner)
+            | Self::Variadic(inner)
+            | Self::Array(inner)
+            | Self::ArrayOfLen(inner, _) => {
+                inner.resolve_self(ty)
+            }
+            Self::Enum(_, types) | Self::Tuple(types) | Self::Union(types) | Self::Intersection(types) => {
+                types.iter_mut().for_each(|v| v.resolve_self(ty))
+            }
+            Self::Struct(entries) => entries.values_mut().for_each(|v| v.resolve_self(ty)),
+            Self::Map(key, value) => {
+                key.resolve_self(ty);
+                value.resolve_self(ty);
+            }
+            Self::Function { params, returns, overloads, .. } => {
+                Func::resolve_signature_self(params, returns, ty);
+                for (params, returns) in overloads.iter_mut() {
+                    Func::resolve_signature_self(params, returns, ty);
+                }
+            }
+            Self::Single(_) | Self::Class(_) | Self::Module(_) => {}
+        }
+    }
+
+    /// Compare the shape of two types ignoring documentation and parameter and return names
+    ///
+    /// [`PartialEq`] compares everything, including docs, which is what definition merging and
+    /// content hashing rely on. Structural equality is used where only the written type matters,
+    /// i.e. to skip duplicate union members that only differ by their docs or param names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::Type;
+    ///
+    /// let named = Type::callback([("ev", Type::single("string"))], []);
+    /// let unnamed = Type::callback([Type::single("string")], []);
+    ///
+    /// assert_ne!(named, unnamed);
+    /// assert!(named.structurally_eq(&unnamed));
+    /// ```
+    pub fn structurally_eq(&self, other: &Type) -> bool {
+        fn all<T>(a: &[T], b: &[T], eq: impl Fn(&T, &T) -> bool) -> bool {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| eq(a, b))
+        }
+
+        fn keyed<K: Ord, V>(a: &BTreeMap<K, V>, b: &BTreeMap<K, V>, eq: impl Fn(&V, &V) -> bool) -> bool {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|((ak, av), (bk, bv))| ak == bk && eq(av, bv))
+        }
+
+        fn field(a: &Field, b: &Field) -> bool {
+            a.visibility == b.visibility && a.ty.structurally_eq(&b.ty)
+        }
+
+        fn signature(a: (&Vec<Param>, &Vec<Return>), b: (&Vec<Param>, &Vec<Return>)) -> bool {
+            all(a.0, b.0, |a, b| a.optional == b.optional && a.ty.structurally_eq(&b.ty))
+                && all(a.1, b.1, |a, b| a.ty.structurally_eq(&b.ty))
+        }
+
+        fn func(a: &Func, b: &Func) -> bool {
+            a.is_async == b.is_async
+                && signature((&a.params, &a.returns), (&b.params, &b.returns))
+                && all(&a.overloads, &b.overloads, |a, b| signature((&a.0, &a.1), (&b.0, &b.1)))
+        }
+
+        fn module(a: &TypedModuleBuilder, b: &TypedModuleBuilder) -> bool {
+            keyed(&a.fields, &b.fields, field)
+                && keyed(&a.meta_fields, &b.meta_fields, field)
+                && keyed(&a.nested_modules, &b.nested_modules, module)
+                && keyed(&a.functions, &b.functions, func)
+                && keyed(&a.methods, &b.methods, func)
+                && keyed(&a.meta_functions, &b.meta_functions, func)
+                && keyed(&a.meta_methods, &b.meta_methods, func)
+        }
+
+        match (self, other) {
+            (Self::Single(a), Self::Single(b)) => a == b,
+            (Self::Value(a), Self::Value(b))
+            | (Self::Alias(a), Self::Alias(b))
+            | (Self::Variadic(a), Self::Variadic(b))
+            | (Self::Array(a), Self::Array(b)) => a.structurally_eq(b),
+            (Self::ArrayOfLen(a, al), Self::ArrayOfLen(b, bl)) => al == bl && a.structurally_eq(b),
+            (Self::Map(ak, av), Self::Map(bk, bv)) => ak.structurally_eq(bk) && av.structurally_eq(bv),
+            (Self::Enum(an, a), Self::Enum(bn, b)) => an == bn && all(a, b, Type::structurally_eq),
+            (Self::Tuple(a), Self::Tuple(b))
+            | (Self::Union(a), Self::Union(b))
+            | (Self::Intersection(a), Self::Intersection(b)) => all(a, b, Type::structurally_eq),
+            (Self::Struct(a), Self::Struct(b)) => keyed(a, b, Type::structurally_eq),
+            (Self::Class(a), Self::Class(b)) => {
+                a.exact == b.exact
+                    && keyed(&a.fields, &b.fields, field)
+                    && keyed(&a.static_fields, &b.static_fields, field)
+                    && keyed(&a.meta_fields, &b.meta_fields, field)
+                    && keyed(&a.methods, &b.methods, func)
+                    && keyed(&a.meta_methods, &b.meta_methods, func)
+                    && keyed(&a.functions, &b.functions, func)
+                    && keyed(&a.meta_functions, &b.meta_functions, func)
+            }
+            (Self::Module(a), Self::Module(b)) => module(a, b),
+            (Self::SelfType, Self::SelfType) => true,
+            (
+                Self::Function { params: ap, returns: ar, overloads: ao, .. },
+                Self::Function { params: bp, returns: br, overloads: bo, .. },
+            ) => {
+                signature((ap, ar), (bp, br))
+                    && all(ao, bo, |a, b| signature((&a.0, &a.1), (&b.0, &b.1)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Recursively simplify and flatten union members into `members` skipping duplicates
+    ///
+    /// Members are compared with [`structurally_eq`][Type::structurally_eq] so the first of several
+    /// members that only differ by docs or names is kept
+    fn flatten_union(types: Vec<Type>, members: &mut Vec<Type>) {
+        for ty in types {
+            match ty.simplify() {
+                Self::Union(nested) => Self::flatten_union(nested, members),
+                other => {
+                    if !members.iter().any(|member| member.structurally_eq(&other)) {
+                        members.push(other);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collapse boolean literals in a list of union members
+    ///
+    /// `true | false` becomes `boolean` and any boolean literal unioned with `boolean` is
+    /// dropped in favor of `boolean`.
+    fn collapse_booleans(types: &mut Vec<Type>) {
+        let is_literal = |ty: &Type| matches!(ty, Type::Single(v) if v == "true" || v == "false");
+        let has = |types: &[Type], name: &str| types.iter().any(|ty| matches!(ty, Type::Single(v) if v == name));
+
+        if has(types, "boolean") {
+            types.retain(|ty| !is_literal(ty));
+        } else if has(types, "true") && has(types, "false") {
+            if let Some(index) = types.iter().position(is_literal) {
+                types[index] = Type::single("boolean");
+            }
+            types.retain(|ty| !is_literal(ty));
         }
     }
 }
@@ -369,6 +1025,7 @@ pub trait TypedMultiValue {
             .into_iter()
             .map(|v| Return {
                 doc: None,
+                name: None,
                 ty: v.ty,
             })
             .collect::<Vec<_>>()
@@ -379,15 +1036,6 @@ pub trait TypedMultiValue {
 }
 
 macro_rules! impl_typed_multi_value {
-    () => (
-        impl TypedMultiValue for () {
-            #[allow(unused_mut)]
-            #[allow(non_snake_case)]
-            fn get_types_as_params() -> Vec<Param> {
-                Vec::new()
-            }
-        }
-    );
     ($($name:ident) +) => (
         impl<$($name,)* > TypedMultiValue for ($($name,)*)
             where $($name: Typed,)*
@@ -408,6 +1056,10 @@ where
     A: Typed,
 {
     fn get_types_as_params() -> Vec<Param> {
+        // `()` is typed as `nil` but represents no values in a parameter or return list
+        if A::IS_UNIT {
+            return Vec::new();
+        }
         Vec::from([A::as_param()])
     }
 }
@@ -428,19 +1080,244 @@ impl_typed_multi_value!(A B C D);
 impl_typed_multi_value!(A B C);
 impl_typed_multi_value!(A B);
 impl_typed_multi_value!(A);
-impl_typed_multi_value!();
+
+/// Visibility of a lua `class` field, i.e. `--- @field private name type`
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum Visibility {
+    #[default]
+    Public,
+    Protected,
+    Private,
+    Package,
+}
+
+impl Visibility {
+    /// Annotation keyword for the visibility, `None` for [`Visibility::Public`] which is implied
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            Self::Public => None,
+            Self::Protected => Some("protected"),
+            Self::Private => Some("private"),
+            Self::Package => Some("package"),
+        }
+    }
+}
 
 /// Type information for a lua `class` field
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Field {
     pub ty: Type,
     pub doc: Option<Cow<'static, str>>,
+    /// Visibility rendered on the `--- @field` annotation: Default [`Visibility::Public`]
+    pub visibility: Visibility,
 }
 
 /// Type information for a lua `class` function
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Func {
     pub params: Vec<Param>,
     pub returns: Vec<Return>,
+    /// Additional signatures rendered as `--- @overload`
+    pub overloads: Vec<(Vec<Param>, Vec<Return>)>,
+    /// Usage examples each rendered as a `--- @usage` block
+    pub usage: Vec<Cow<'static, str>>,
+    /// Whether the function was registered as async, rendered as `--- @async`
+    pub is_async: bool,
     pub doc: Option<Cow<'static, str>>,
 }
+
+impl Func {
+    /// Replace every [`Type::SelfType`] placeholder in the signature and its overloads with `ty`
+    pub fn resolve_self(&mut self, ty: &Type) {
+        Self::resolve_signature_self(&mut self.params, &mut self.returns, ty);
+        for (params, returns) in self.overloads.iter_mut() {
+            Self::resolve_signature_self(params, returns, ty);
+        }
+    }
+
+    fn resolve_signature_self(params: &mut [Param], returns: &mut [Return], ty: &Type) {
+        params.iter_mut().for_each(|v| v.ty.resolve_self(ty));
+        returns.iter_mut().for_each(|v| v.ty.resolve_self(ty));
+    }
+}
+
+/// Render a rust value as a lua type literal. i.e. `"1.2.3"`, `3`, `true`
+///
+/// Returns `None` when the value can't be written as a literal so the caller can fall back to the
+/// value's [`Typed`] type
+pub trait IntoLuaTypeLiteral {
+    fn to_type_literal(&self) -> Option<Type>;
+}
+
+macro_rules! impl_to_type_literal {
+    ($($target: ty),* $(,)?) => {
+        $(
+            impl IntoLuaTypeLiteral for $target {
+                fn to_type_literal(&self) -> Option<Type> {
+                    Some(Type::literal(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_type_literal!(u8, u16, u32, u64, usize, u128, i8, i16, i32, i64, isize, i128, bool);
+
+impl IntoLuaTypeLiteral for f32 {
+    fn to_type_literal(&self) -> Option<Type> {
+        (*self as f64).to_type_literal()
+    }
+}
+
+impl IntoLuaTypeLiteral for f64 {
+    fn to_type_literal(&self) -> Option<Type> {
+        self.is_finite().then(|| Type::literal(self))
+    }
+}
+
+impl IntoLuaTypeLiteral for str {
+    fn to_type_literal(&self) -> Option<Type> {
+        (!self.contains(['"', '\\', '\n', '\r'])).then(|| Type::literal_string(self))
+    }
+}
+
+impl IntoLuaTypeLiteral for &str {
+    fn to_type_literal(&self) -> Option<Type> {
+        str::to_type_literal(self)
+    }
+}
+
+impl IntoLuaTypeLiteral for String {
+    fn to_type_literal(&self) -> Option<Type> {
+        self.as_str().to_type_literal()
+    }
+}
+
+impl IntoLuaTypeLiteral for Cow<'_, str> {
+    fn to_type_literal(&self) -> Option<Type> {
+        self.as_ref().to_type_literal()
+    }
+}
+
+/// Conversion into an optional doc comment
+///
+/// `()` and `None` represent no doc comment, while collections of lines are joined with a newline
+pub trait IntoDocComment {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>>;
+}
+
+impl IntoDocComment for () {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        None
+    }
+}
+
+impl IntoDocComment for String {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        Some(self.into())
+    }
+}
+
+impl IntoDocComment for &str {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        Some(self.to_string().into())
+    }
+}
+
+impl IntoDocComment for Cow<'static, str> {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        Some(self)
+    }
+}
+
+impl<D: IntoDocComment> IntoDocComment for Option<D> {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        self.and_then(IntoDocComment::into_doc_comment)
+    }
+}
+
+impl IntoDocComment for Vec<String> {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        (!self.is_empty()).then(|| self.join("\n").into())
+    }
+}
+
+impl IntoDocComment for &[&str] {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        (!self.is_empty()).then(|| self.join("\n").into())
+    }
+}
+
+impl<const N: usize> IntoDocComment for [&str; N] {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        self.as_slice().into_doc_comment()
+    }
+}
+
+/// Doc comment split into a summary, a longer description, and annotation tags
+///
+/// Renders as the summary and description separated by a blank line followed by each tag as
+/// `@{name} {value}`, i.e. `@see other` or `@deprecated`
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Documentation {
+    pub summary: Option<Cow<'static, str>>,
+    pub description: Option<Cow<'static, str>>,
+    pub tags: Vec<(Cow<'static, str>, Option<Cow<'static, str>>)>,
+}
+
+impl IntoDocComment for Documentation {
+    fn into_doc_comment(self) -> Option<Cow<'static, str>> {
+        let tags = self
+            .tags
+            .iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("@{name} {value}"),
+                None => format!("@{name}"),
+            })
+            .collect::<Vec<_>>();
+
+        let sections = [
+            self.summary.map(|v| v.to_string()),
+            self.description.map(|v| v.to_string()),
+            (!tags.is_empty()).then(|| tags.join("\n")),
+        ];
+        sections
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            .into_doc_comment()
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Builder state that is only used while building, i.e. the member that was added last
+///
+/// Every value compares as equal and nothing is hashed, so the state doesn't affect comparing or
+/// hashing the builder that holds it
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct Transient<T>(pub(crate) T);
+
+impl<T> PartialEq for Transient<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Transient<T> {}
+
+impl<T> PartialOrd for Transient<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Transient<T> {
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        std::cmp::Ordering::Equal
+    }
+}
+
+impl<T> std::hash::Hash for Transient<T> {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
@@ -1,6 +1,6 @@
-use std::{any::type_name, borrow::Cow, collections::BTreeMap};
+use std::{any::type_name, borrow::Cow, collections::{BTreeMap, HashSet}};
 
-use super::{generator::FunctionBuilder, Field, Func, Typed, TypedMultiValue};
+use super::{generator::FunctionBuilder, Field, Func, Transient, Type, Typed, TypedMultiValue, Visibility};
 use crate::{
     extras::{Module, ModuleFields, ModuleMethods},
     MaybeSend,
@@ -8,7 +8,7 @@ use crate::{
 use mlua::{FromLuaMulti, IntoLua, IntoLuaMulti};
 
 /// Builder that constructs type and documentation information for a module using the [`TypedModule`] trait
-#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TypedModuleBuilder {
     pub doc: Option<Cow<'static, str>>,
 
@@ -23,9 +23,23 @@ pub struct TypedModuleBuilder {
     pub meta_methods: BTreeMap<Cow<'static, str>, Func>,
 
     queued_doc: Option<String>,
+    /// Member that was added last, which [`document_last`][TypedModuleBuilder::document_last] documents
+    last_member: Transient<Option<(ModuleMember, Cow<'static, str>)>>,
     parents: Vec<&'static str>,
 }
 
+/// The kind of member a module entry was added as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum ModuleMember {
+    Module,
+    Field,
+    MetaField,
+    Function,
+    Method,
+    MetaFunction,
+    MetaMethod,
+}
+
 impl TypedModuleBuilder {
     pub fn new<M: TypedModule>() -> mlua::Result<Self> {
         let mut builder = TypedModuleBuilder::default();
@@ -40,6 +54,240 @@ impl TypedModuleBuilder {
         Ok(builder)
     }
 
+    /// Approximate the type information of an existing lua table
+    ///
+    /// Each string key becomes a field typed with [`Type::from_value`], i.e. `string`, `integer`, or
+    /// `fun()` since function signatures can't be recovered. Nested tables become nested modules
+    /// unless they were already visited, in which case the field is typed as `table`. Other keys and
+    /// the table's metatable are ignored.
+    pub fn from_table(table: &mlua::Table<'_>) -> mlua::Result<Self> {
+        let mut visited = HashSet::new();
+        Self::from_table_visited(table, &mut visited)
+    }
+
+    fn from_table_visited(
+        table: &mlua::Table<'_>,
+        visited: &mut HashSet<*const std::ffi::c_void>,
+    ) -> mlua::Result<Self> {
+        visited.insert(table.to_pointer());
+
+        let mut builder = TypedModuleBuilder::default();
+        for pair in table.clone().pairs::<mlua::Value, mlua::Value>() {
+            let (key, value) = pair?;
+            let mlua::Value::String(key) = key else {
+                continue;
+            };
+            let name: Cow<'static, str> = key.to_str()?.to_string().into();
+
+            let ty = match value {
+                mlua::Value::Nil => continue,
+                mlua::Value::Table(nested) if !visited.contains(&nested.to_pointer()) => {
+                    let nested = Self::from_table_visited(&nested, visited)?;
+                    builder.nested_modules.insert(name, nested);
+                    continue;
+                }
+                mlua::Value::Table(_) => Type::single("table"),
+                other => Type::from_value(&other),
+            };
+            builder.fields.insert(name, Field { ty, doc: None, visibility: Visibility::default() });
+        }
+        Ok(builder)
+    }
+
+    /// Hand assemble a module's type information without declaring a [`TypedModule`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Type, TypedModuleBuilder, TypedModuleFields};
+    ///
+    /// let module = TypedModuleBuilder::build(|module| {
+    ///     module
+    ///         .field("version", Type::single("string"))
+    ///         .function::<String, bool>("exists");
+    ///     TypedModuleFields::document(module, "Greet someone by name");
+    ///     module.function::<String, String>("greet");
+    /// });
+    ///
+    /// assert!(module.fields.contains_key("version"));
+    /// assert!(module.functions["greet"].doc.is_some());
+    /// ```
+    pub fn build<F>(build: F) -> Self
+    where
+        F: FnOnce(&mut TypedModuleBuilder),
+    {
+        let mut builder = TypedModuleBuilder::default();
+        build(&mut builder);
+        builder
+    }
+
+    /// Add a field with the given type. Uses the queued doc comment
+    pub fn field(&mut self, name: impl AsRef<str>, ty: Type) -> &mut Self {
+        let name = self.member_name(ModuleMember::Field, name.as_ref());
+        self.fields.insert(
+            name,
+            Field {
+                ty,
+                doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
+            },
+        );
+        self
+    }
+
+    /// Add a function with the given params and returns. Uses the queued doc comment
+    pub fn function<A: TypedMultiValue, R: TypedMultiValue>(&mut self, name: impl AsRef<str>) -> &mut Self {
+        let func = self.signature::<A, R>();
+        let name = self.member_name(ModuleMember::Function, name.as_ref());
+        self.functions.insert(name, func);
+        self
+    }
+
+    /// Add a method with the given params and returns. Uses the queued doc comment
+    pub fn method<A: TypedMultiValue, R: TypedMultiValue>(&mut self, name: impl AsRef<str>) -> &mut Self {
+        let func = self.signature::<A, R>();
+        let name = self.member_name(ModuleMember::Method, name.as_ref());
+        self.methods.insert(name, func);
+        self
+    }
+
+    fn signature<A: TypedMultiValue, R: TypedMultiValue>(&mut self) -> Func {
+        Func {
+            params: A::get_types_as_params(),
+            returns: R::get_types_as_returns(),
+            overloads: Vec::new(),
+            usage: Vec::new(),
+            is_async: false,
+            doc: self.queued_doc.take().map(|v| v.into()),
+        }
+    }
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    pub fn queued_doc(&self) -> Option<&str> {
+        self.queued_doc.as_deref()
+    }
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    pub fn clear_queued_doc(&mut self) -> &mut Self {
+        self.queued_doc = None;
+        self
+    }
+
+    /// Attach a doc comment to the member that was added last instead of queuing it for the next
+    /// member. Does nothing if no member was added yet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::typed::{Type, TypedModuleBuilder};
+    ///
+    /// let mut module = TypedModuleBuilder::default();
+    /// module
+    ///     .field("name", Type::single("string"))
+    ///     .document_last("Name of the module");
+    ///
+    /// assert_eq!(module.fields["name"].doc.as_deref(), Some("Name of the module"));
+    /// ```
+    pub fn document_last(&mut self, doc: impl AsRef<str>) -> &mut Self {
+        let doc = Some(doc.as_ref().to_string().into());
+        if let Some((kind, name)) = &self.last_member.0 {
+            match kind {
+                ModuleMember::Module => {
+                    if let Some(module) = self.nested_modules.get_mut(name) {
+                        module.doc = doc;
+                    }
+                }
+                ModuleMember::Field | ModuleMember::MetaField => {
+                    let fields = match kind {
+                        ModuleMember::Field => &mut self.fields,
+                        _ => &mut self.meta_fields,
+                    };
+                    if let Some(field) = fields.get_mut(name) {
+                        field.doc = doc;
+                    }
+                }
+                _ => {
+                    let funcs = match kind {
+                        ModuleMember::Function => &mut self.functions,
+                        ModuleMember::Method => &mut self.methods,
+                        ModuleMember::MetaFunction => &mut self.meta_functions,
+                        _ => &mut self.meta_methods,
+                    };
+                    if let Some(func) = funcs.get_mut(name) {
+                        func.doc = doc;
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// Name of a member that is being added, remembering it as the member that was added last
+    fn member_name(&mut self, kind: ModuleMember, name: &str) -> Cow<'static, str> {
+        let name: Cow<'static, str> = name.to_string().into();
+        self.last_member = Transient(Some((kind, name.clone())));
+        name
+    }
+
+    /// Build an anonymous nested module inline without declaring a dedicated [`TypedModule`].
+    ///
+    /// Any doc queued with [`document`][TypedModuleFields::document] is used as the nested module's doc.
+    /// This only collects type information; the matching lua table must be created separately.
+    pub fn add_nested<F>(&mut self, name: impl AsRef<str>, build: F) -> &mut Self
+    where
+        F: FnOnce(&mut TypedModuleBuilder),
+    {
+        let mut nested = TypedModuleBuilder {
+            doc: self.queued_doc.take().map(|v| v.into()),
+            parents: self.parents.clone(),
+            ..Default::default()
+        };
+
+        build(&mut nested);
+
+        let name = self.member_name(ModuleMember::Module, name.as_ref());
+        self.nested_modules.insert(name, nested);
+        self
+    }
+
+    /// Replace every [`Type::SelfType`] placeholder in the module's fields and functions with `ty`,
+    /// including nested modules
+    pub fn resolve_self(&mut self, ty: &Type) {
+        for field in self.fields.values_mut().chain(self.meta_fields.values_mut()) {
+            field.ty.resolve_self(ty);
+        }
+
+        for func in self
+            .functions
+            .values_mut()
+            .chain(self.methods.values_mut())
+            .chain(self.meta_functions.values_mut())
+            .chain(self.meta_methods.values_mut())
+        {
+            func.resolve_self(ty);
+        }
+
+        for nested in self.nested_modules.values_mut() {
+            nested.resolve_self(ty);
+        }
+    }
+
+    /// Log a warning about typos in hand written metatable keys, i.e. `__indx`, which LuaLS
+    /// silently ignores. Custom `__` keys are still allowed
+    #[cfg(all(debug_assertions, feature = "log"))]
+    fn check_meta_name(name: &str) {
+        const METAMETHODS: &[&str] = &[
+            "__add", "__sub", "__mul", "__div", "__mod", "__pow", "__unm", "__idiv", "__band",
+            "__bor", "__bxor", "__bnot", "__shl", "__shr", "__concat", "__len", "__eq", "__lt",
+            "__le", "__index", "__newindex", "__call", "__tostring", "__pairs", "__ipairs",
+            "__iter", "__close", "__gc", "__mode", "__name", "__metatable", "__type",
+        ];
+
+        if name.starts_with("__") && !METAMETHODS.contains(&name) {
+            log::warn!("'{name}' is not a known metamethod");
+        }
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.fields.is_empty()
@@ -60,8 +308,21 @@ impl TypedModuleBuilder {
 /// Typed variant of [`ModuleFields`]
 pub trait TypedModuleFields<'lua> {
     /// Queue a doc comment to be used with the nest `add` call
+    ///
+    /// The doc stays queued until something is added, use
+    /// [`clear_queued_doc`][TypedModuleFields::clear_queued_doc] to discard it
     fn document<V: AsRef<str>>(&mut self, doc: V) -> &mut Self;
 
+    /// Adds documentation to the member that was added last instead of the next one, i.e. a
+    /// trailing doc comment
+    fn document_last<V: AsRef<str>>(&mut self, doc: V) -> &mut Self;
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    fn queued_doc(&self) -> Option<&str>;
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    fn clear_queued_doc(&mut self) -> &mut Self;
+
     /// Typed variant of [`add_field`][ModuleFields::add_field] only collecting the type information
     fn add_field<K, V>(&mut self, name: K, value: V) -> mlua::Result<()>
     where
@@ -83,8 +344,21 @@ pub trait TypedModuleFields<'lua> {
 /// Typed variant of [`ModuleMethods`]
 pub trait TypedModuleMethods<'lua> {
     /// Queue a doc comment to be used with the nest `add` call
+    ///
+    /// The doc stays queued until something is added, use
+    /// [`clear_queued_doc`][TypedModuleMethods::clear_queued_doc] to discard it
     fn document<V: AsRef<str>>(&mut self, doc: V) -> &mut Self;
 
+    /// Adds documentation to the member that was added last instead of the next one, i.e. a
+    /// trailing doc comment
+    fn document_last<V: AsRef<str>>(&mut self, doc: V) -> &mut Self;
+
+    /// Get the doc comment queued by `document` that hasn't been consumed yet
+    fn queued_doc(&self) -> Option<&str>;
+
+    /// Discard the queued doc comment so it isn't attached to the next member that gets added
+    fn clear_queued_doc(&mut self) -> &mut Self;
+
     /// Typed variant of [`add_function`][ModuleMethods::add_function] only collecting the type information
     fn add_function<K, F, A, R>(&mut self, name: K, function: F) -> mlua::Result<()>
     where
@@ -136,6 +410,33 @@ pub trait TypedModuleMethods<'lua> {
         G: Fn(&mut FunctionBuilder<A, R>);
 
     /// Typed variant of [`add_method`][ModuleMethods::add_method] only collecting the type information
+    ///
+    /// The table the method is called on is passed as the second argument and `A` is only the
+    /// arguments after it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua::Lua;
+    /// use mlua_extras::{extras::Module, typed::{TypedModule, TypedModuleFields, TypedModuleMethods}};
+    ///
+    /// struct Counter;
+    /// impl TypedModule for Counter {
+    ///     fn add_fields<'lua, F: TypedModuleFields<'lua>>(fields: &mut F) -> mlua::Result<()> {
+    ///         fields.add_field("count", 1)
+    ///     }
+    ///
+    ///     fn add_methods<'lua, M: TypedModuleMethods<'lua>>(methods: &mut M) -> mlua::Result<()> {
+    ///         methods.add_method("add", |_, this, amount: i64| {
+    ///             this.set("count", this.get::<_, i64>("count")? + amount)
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let lua = Lua::new();
+    /// lua.globals().set("counter", Counter::module()).unwrap();
+    /// assert_eq!(lua.load("counter:add(2); return counter.count").eval::<i64>().unwrap(), 3);
+    /// ```
     fn add_method<K, F, A, R>(&mut self, name: K, function: F) -> mlua::Result<()>
     where
         K: AsRef<str>,
@@ -160,6 +461,64 @@ pub trait TypedModuleMethods<'lua> {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         G: Fn(&mut FunctionBuilder<A, R>);
 
+    #[cfg(feature = "async")]
+    /// Typed variant of [`add_async_function`][ModuleMethods::add_async_function] only collecting the type information
+    fn add_async_function<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue;
+
+    #[cfg(feature = "async")]
+    /// Typed variant of [`add_async_function`][ModuleMethods::add_async_function] only collecting the type information
+    ///
+    /// Pass an additional callback that allows for param names, param doc comments, and return doc
+    /// comments to be specified.
+    fn add_async_function_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        function: F,
+        generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>);
+
+    #[cfg(feature = "async")]
+    /// Typed variant of [`add_async_method`][ModuleMethods::add_async_method] only collecting the type information
+    fn add_async_method<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue;
+
+    #[cfg(feature = "async")]
+    /// Typed variant of [`add_async_method`][ModuleMethods::add_async_method] only collecting the type information
+    ///
+    /// Pass an additional callback that allows for param names, param doc comments, and return doc
+    /// comments to be specified.
+    fn add_async_method_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        function: F,
+        generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>);
+
     /// Typed variant of [`add_meta_method`][ModuleMethods::add_meta_method] only collecting the type information
     fn add_meta_method<K, F, A, R>(&mut self, name: K, function: F) -> mlua::Result<()>
     where
@@ -192,6 +551,18 @@ impl<'module, 'lua, M: ModuleFields<'lua>> TypedModuleFields<'lua> for WrappedMo
         self
     }
 
+    fn document_last<V: AsRef<str>>(&mut self, _doc: V) -> &mut Self {
+        self
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        None
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        self
+    }
+
     fn add_field<K, V>(&mut self, name: K, value: V) -> mlua::Result<()>
     where
         K: AsRef<str>,
@@ -221,6 +592,18 @@ impl<'module, 'lua, M: ModuleMethods<'lua>> TypedModuleMethods<'lua> for Wrapped
         self
     }
 
+    fn document_last<V: AsRef<str>>(&mut self, _doc: V) -> &mut Self {
+        self
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        None
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        self
+    }
+
     fn add_function<K, F, A, R>(&mut self, name: K, function: F) -> mlua::Result<()>
     where
         K: AsRef<str>,
@@ -303,6 +686,66 @@ impl<'module, 'lua, M: ModuleMethods<'lua>> TypedModuleMethods<'lua> for Wrapped
             .add_method::<&str, F, A, R>(name.as_ref(), function)
     }
 
+    #[cfg(feature = "async")]
+    fn add_async_function<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+    {
+        self.0.add_async_function::<&str, F, A, R, FR>(name.as_ref(), function)
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_function_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        function: F,
+        _generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>),
+    {
+        self.0.add_async_function::<&str, F, A, R, FR>(name.as_ref(), function)
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_method<K, F, A, R, FR>(&mut self, name: K, function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+    {
+        self.0.add_async_method::<&str, F, A, R, FR>(name.as_ref(), function)
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_method_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        function: F,
+        _generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>),
+    {
+        self.0.add_async_method::<&str, F, A, R, FR>(name.as_ref(), function)
+    }
+
     fn add_meta_method<K, F, A, R>(&mut self, name: K, function: F) -> mlua::Result<()>
     where
         K: AsRef<str>,
@@ -337,6 +780,18 @@ impl<'lua> TypedModuleFields<'lua> for TypedModuleBuilder {
         self
     }
 
+    fn document_last<V: AsRef<str>>(&mut self, doc: V) -> &mut Self {
+        TypedModuleBuilder::document_last(self, doc)
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        TypedModuleBuilder::queued_doc(self)
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        TypedModuleBuilder::clear_queued_doc(self)
+    }
+
     fn add_module<V>(&mut self, name: impl AsRef<str>) -> mlua::Result<()>
     where
         V: TypedModule,
@@ -365,7 +820,8 @@ impl<'lua> TypedModuleFields<'lua> for TypedModuleBuilder {
         V::add_fields(&mut nested)?;
         V::add_methods(&mut nested)?;
 
-        self.nested_modules.insert(name.as_ref().to_string().into(), nested);
+        let name = self.member_name(ModuleMember::Module, name.as_ref());
+        self.nested_modules.insert(name, nested);
         Ok(())
     }
 
@@ -374,11 +830,13 @@ impl<'lua> TypedModuleFields<'lua> for TypedModuleBuilder {
         K: AsRef<str>,
         V: IntoLua<'lua> + Typed,
     {
+        let name = self.member_name(ModuleMember::Field, name.as_ref());
         self.fields.insert(
-            name.as_ref().to_string().into(),
+            name,
             Field {
                 ty: V::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             },
         );
         Ok(())
@@ -389,11 +847,16 @@ impl<'lua> TypedModuleFields<'lua> for TypedModuleBuilder {
         K: AsRef<str>,
         V: IntoLua<'lua> + Typed,
     {
+        #[cfg(all(debug_assertions, feature = "log"))]
+        Self::check_meta_name(name.as_ref());
+
+        let name = self.member_name(ModuleMember::MetaField, name.as_ref());
         self.meta_fields.insert(
-            name.as_ref().to_string().into(),
+            name,
             Field {
                 ty: V::ty(),
                 doc: self.queued_doc.take().map(|v| v.into()),
+                visibility: Visibility::default(),
             },
         );
         Ok(())
@@ -406,6 +869,18 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         self
     }
 
+    fn document_last<V: AsRef<str>>(&mut self, doc: V) -> &mut Self {
+        TypedModuleBuilder::document_last(self, doc)
+    }
+
+    fn queued_doc(&self) -> Option<&str> {
+        TypedModuleBuilder::queued_doc(self)
+    }
+
+    fn clear_queued_doc(&mut self) -> &mut Self {
+        TypedModuleBuilder::clear_queued_doc(self)
+    }
+
     fn add_function<K, F, A, R>(&mut self, name: K, _function: F) -> mlua::Result<()>
     where
         K: AsRef<str>,
@@ -413,11 +888,15 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         A: FromLuaMulti<'lua> + TypedMultiValue,
         R: IntoLuaMulti<'lua> + TypedMultiValue,
     {
+        let name = self.member_name(ModuleMember::Function, name.as_ref());
         self.functions.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -440,11 +919,15 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
+        let name = self.member_name(ModuleMember::Function, name.as_ref());
         self.functions.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -458,11 +941,18 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         A: FromLuaMulti<'lua> + TypedMultiValue,
         R: IntoLuaMulti<'lua> + TypedMultiValue,
     {
+        #[cfg(all(debug_assertions, feature = "log"))]
+        Self::check_meta_name(name.as_ref());
+
+        let name = self.member_name(ModuleMember::MetaFunction, name.as_ref());
         self.meta_functions.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -482,14 +972,21 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         G: Fn(&mut FunctionBuilder<A, R>),
     {
+        #[cfg(all(debug_assertions, feature = "log"))]
+        Self::check_meta_name(name.as_ref());
+
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
+        let name = self.member_name(ModuleMember::MetaFunction, name.as_ref());
         self.meta_functions.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -503,11 +1000,15 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         A: FromLuaMulti<'lua> + TypedMultiValue,
         R: IntoLuaMulti<'lua> + TypedMultiValue,
     {
+        let name = self.member_name(ModuleMember::Method, name.as_ref());
         self.methods.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -530,11 +1031,129 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
+        let name = self.member_name(ModuleMember::Method, name.as_ref());
+        self.methods.insert(
+            name,
+            Func {
+                params: builder.params,
+                returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
+                doc: self.queued_doc.take().map(|v| v.into()),
+            },
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_function<K, F, A, R, FR>(&mut self, name: K, _function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+    {
+        let name = self.member_name(ModuleMember::Function, name.as_ref());
+        self.functions.insert(
+            name,
+            Func {
+                params: A::get_types_as_params(),
+                returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: true,
+                doc: self.queued_doc.take().map(|v| v.into()),
+            },
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_function_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        _function: F,
+        generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>),
+    {
+        let mut builder = FunctionBuilder::<A, R>::default();
+        generator(&mut builder);
+
+        let name = self.member_name(ModuleMember::Function, name.as_ref());
+        self.functions.insert(
+            name,
+            Func {
+                params: builder.params,
+                returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: true,
+                doc: self.queued_doc.take().map(|v| v.into()),
+            },
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_method<K, F, A, R, FR>(&mut self, name: K, _function: F) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+    {
+        let name = self.member_name(ModuleMember::Method, name.as_ref());
+        self.methods.insert(
+            name,
+            Func {
+                params: A::get_types_as_params(),
+                returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: true,
+                doc: self.queued_doc.take().map(|v| v.into()),
+            },
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    fn add_async_method_with<K, F, A, R, FR, G>(
+        &mut self,
+        name: K,
+        _function: F,
+        generator: G,
+    ) -> mlua::Result<()>
+    where
+        K: AsRef<str>,
+        F: Fn(&'lua mlua::Lua, mlua::Table<'lua>, A) -> FR + MaybeSend + 'static,
+        FR: std::future::Future<Output = mlua::Result<R>> + 'lua,
+        A: FromLuaMulti<'lua> + TypedMultiValue,
+        R: IntoLuaMulti<'lua> + TypedMultiValue,
+        G: Fn(&mut FunctionBuilder<A, R>),
+    {
+        let mut builder = FunctionBuilder::<A, R>::default();
+        generator(&mut builder);
+
+        let name = self.member_name(ModuleMember::Method, name.as_ref());
         self.methods.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: true,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -548,11 +1167,18 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         A: FromLuaMulti<'lua> + TypedMultiValue,
         R: IntoLuaMulti<'lua> + TypedMultiValue,
     {
+        #[cfg(all(debug_assertions, feature = "log"))]
+        Self::check_meta_name(name.as_ref());
+
+        let name = self.member_name(ModuleMember::MetaMethod, name.as_ref());
         self.meta_methods.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: A::get_types_as_params(),
                 returns: R::get_types_as_returns(),
+                overloads: Vec::new(),
+                usage: Vec::new(),
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -572,14 +1198,21 @@ impl<'lua> TypedModuleMethods<'lua> for TypedModuleBuilder {
         R: IntoLuaMulti<'lua> + TypedMultiValue,
         G: Fn(&mut FunctionBuilder<A, R>),
     {
+        #[cfg(all(debug_assertions, feature = "log"))]
+        Self::check_meta_name(name.as_ref());
+
         let mut builder = FunctionBuilder::<A, R>::default();
         generator(&mut builder);
 
+        let name = self.member_name(ModuleMember::MetaMethod, name.as_ref());
         self.meta_methods.insert(
-            name.as_ref().to_string().into(),
+            name,
             Func {
                 params: builder.params,
                 returns: builder.returns,
+                overloads: builder.overloads,
+                usage: builder.usage,
+                is_async: false,
                 doc: self.queued_doc.take().map(|v| v.into()),
             },
         );
@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use mlua::{FromLua, IntoLua, Lua, Table, Value};
+
+use crate::extras::Require;
+
+use super::{Type, Typed, TypedModule, TypedModuleBuilder};
+
+/// Helper to bake the shape of a lua [`Table`] using a [`TypedModule`] as the schema. Field access is
+/// validated against the fields, functions, methods, and nested modules declared by the schema.
+pub struct TypedTable<'lua, S>
+where
+    S: TypedModule,
+{
+    inner: Table<'lua>,
+    schema: TypedModuleBuilder,
+    _s: PhantomData<S>,
+}
+
+impl<'lua, S> TypedTable<'lua, S>
+where
+    S: TypedModule,
+{
+    /// Wrap a lua [`Table`] with the schema from `S`
+    pub fn new(table: Table<'lua>) -> mlua::Result<Self> {
+        Ok(Self {
+            inner: table,
+            schema: TypedModuleBuilder::new::<S>()?,
+            _s: PhantomData,
+        })
+    }
+
+    /// Get a field using a `.` separated path, i.e. `nested.name`
+    ///
+    /// Errors if the field, function, method, or nested module is not declared by the schema
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mlua_extras::{
+    ///     extras::Module,
+    ///     mlua::{self, Function, Lua, Table},
+    ///     typed::{TypedModule, TypedModuleFields, TypedModuleMethods, TypedTable},
+    /// };
+    ///
+    /// struct Builder;
+    /// impl TypedModule for Builder {
+    ///     fn add_fields<'lua, F: TypedModuleFields<'lua>>(fields: &mut F) -> mlua::Result<()> {
+    ///         fields.add_field("name", "builder")
+    ///     }
+    ///
+    ///     fn add_methods<'lua, M: TypedModuleMethods<'lua>>(methods: &mut M) -> mlua::Result<()> {
+    ///         methods.add_method("chain", |_, _this, ()| Ok(()))
+    ///     }
+    /// }
+    ///
+    /// let lua = Lua::new();
+    /// lua.globals().set("builder", Builder::module())?;
+    /// let table = TypedTable::<Builder>::new(lua.globals().get::<_, Table>("builder")?)?;
+    ///
+    /// assert_eq!(table.get_field::<String>("name")?, "builder");
+    /// assert!(table.get_field::<Function>("chain").is_ok());
+    /// assert!(table.get_field::<Function>("missing").is_err());
+    /// # Ok::<_, mlua::Error>(())
+    /// ```
+    pub fn get_field<V: FromLua<'lua>>(&'lua self, path: impl AsRef<str>) -> mlua::Result<V> {
+        self.validate(path.as_ref())?;
+        self.inner.require::<V>(path)
+    }
+
+    /// Set a field using a `.` separated path, i.e. `nested.name`
+    ///
+    /// Errors if the field is not declared by the schema
+    pub fn set_field<V: IntoLua<'lua>>(&'lua self, path: impl AsRef<str>, value: V) -> mlua::Result<()> {
+        let path = path.as_ref();
+        self.validate(path)?;
+
+        match path.rsplit_once('.') {
+            Some((parent, name)) => self.inner.require::<Table>(parent)?.set(name, value),
+            None => self.inner.set(path, value),
+        }
+    }
+
+    /// Get the underlying lua [`Table`]
+    pub fn table(&self) -> &Table<'lua> {
+        &self.inner
+    }
+
+    fn validate(&self, path: &str) -> mlua::Result<()> {
+        let segments = path
+            .split('.')
+            .filter_map(|v| (!v.trim().is_empty()).then_some(v.trim()))
+            .collect::<Vec<_>>();
+
+        let mut module = &self.schema;
+        if let Some((last, parents)) = segments.split_last() {
+            for seg in parents {
+                module = module.nested_modules.get(*seg).ok_or_else(|| {
+                    mlua::Error::runtime(format!("undeclared nested module '{seg}' in path: {path:?}"))
+                })?;
+            }
+
+            let declared = module.fields.contains_key(*last)
+                || module.meta_fields.contains_key(*last)
+                || module.functions.contains_key(*last)
+                || module.methods.contains_key(*last)
+                || module.nested_modules.contains_key(*last);
+            if declared {
+                return Ok(());
+            }
+        }
+
+        Err(mlua::Error::runtime(format!("undeclared field: {path:?}")))
+    }
+}
+
+impl<'lua, S> FromLua<'lua> for TypedTable<'lua, S>
+where
+    S: TypedModule,
+{
+    fn from_lua(value: Value<'lua>, lua: &'lua Lua) -> mlua::prelude::LuaResult<Self> {
+        Self::new(FromLua::from_lua(value, lua)?)
+    }
+}
+
+impl<'lua, S> IntoLua<'lua> for TypedTable<'lua, S>
+where
+    S: TypedModule,
+{
+    fn into_lua(self, _lua: &'lua Lua) -> mlua::prelude::LuaResult<Value<'lua>> {
+        Ok(Value::Table(self.inner))
+    }
+}
+
+impl<'lua, S> Typed for TypedTable<'lua, S>
+where
+    S: TypedModule,
+{
+    /// The schema's module type, falling back to `table` if the schema fails to build
+    fn ty() -> Type {
+        TypedModuleBuilder::new::<S>()
+            .map(Type::module)
+            .unwrap_or_else(|_| Type::single("table"))
+    }
+}
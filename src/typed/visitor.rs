@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use super::{function::Return, Func, Param, Type, TypedClassBuilder, TypedModuleBuilder};
+
+/// Walks a [`Type`] recursively. Start the traversal with [`Type::accept`]
+///
+/// Every method has a default implementation that visits the nested types, so a visitor only
+/// needs to override the methods for the types it cares about. Call the default traversal from an
+/// override with `ty.accept(self)` on each nested type to keep walking.
+///
+/// # Example
+///
+/// ```
+/// use mlua_extras::typed::{Type, TypeVisitor};
+///
+/// #[derive(Default)]
+/// struct Names(Vec<String>);
+///
+/// impl TypeVisitor for Names {
+///     fn visit_single(&mut self, name: &str) {
+///         self.0.push(name.to_string());
+///     }
+/// }
+///
+/// let mut names = Names::default();
+/// Type::array(Type::single("string") | Type::single("integer")).accept(&mut names);
+/// assert_eq!(names.0, ["string", "integer"]);
+/// ```
+pub trait TypeVisitor {
+    fn visit_single(&mut self, _name: &str) {}
+
+    fn visit_value(&mut self, ty: &Type) {
+        ty.accept(self)
+    }
+
+    fn visit_alias(&mut self, ty: &Type) {
+        ty.accept(self)
+    }
+
+    fn visit_enum(&mut self, _name: &str, types: &[Type]) {
+        types.iter().for_each(|v| v.accept(self))
+    }
+
+    fn visit_class(&mut self, class: &TypedClassBuilder) {
+        for field in class
+            .fields
+            .values()
+            .chain(class.static_fields.values())
+            .chain(class.meta_fields.values())
+        {
+            field.ty.accept(self);
+        }
+
+        for func in class
+            .methods
+            .values()
+            .chain(class.meta_methods.values())
+            .chain(class.functions.values())
+            .chain(class.meta_functions.values())
+        {
+            self.visit_func(func);
+        }
+    }
+
+    fn visit_module(&mut self, module: &TypedModuleBuilder) {
+        for field in module.fields.values().chain(module.meta_fields.values()) {
+            field.ty.accept(self);
+        }
+
+        for func in module
+            .functions
+            .values()
+            .chain(module.methods.values())
+            .chain(module.meta_functions.values())
+            .chain(module.meta_methods.values())
+        {
+            self.visit_func(func);
+        }
+
+        for nested in module.nested_modules.values() {
+            self.visit_module(nested);
+        }
+    }
+
+    fn visit_tuple(&mut self, types: &[Type]) {
+        types.iter().for_each(|v| v.accept(self))
+    }
+
+    fn visit_struct(&mut self, entries: &BTreeMap<&'static str, Type>) {
+        entries.values().for_each(|v| v.accept(self))
+    }
+
+    fn visit_variadic(&mut self, ty: &Type) {
+        ty.accept(self)
+    }
+
+    fn visit_union(&mut self, types: &[Type]) {
+        types.iter().for_each(|v| v.accept(self))
+    }
+
+    fn visit_intersection(&mut self, types: &[Type]) {
+        types.iter().for_each(|v| v.accept(self))
+    }
+
+    fn visit_array(&mut self, ty: &Type) {
+        ty.accept(self)
+    }
+
+    fn visit_array_of_len(&mut self, ty: &Type, _len: usize) {
+        ty.accept(self)
+    }
+
+    fn visit_map(&mut self, key: &Type, value: &Type) {
+        key.accept(self);
+        value.accept(self);
+    }
+
+    fn visit_self_type(&mut self) {}
+
+    fn visit_function(
+        &mut self,
+        params: &[Param],
+        returns: &[Return],
+        overloads: &[(Vec<Param>, Vec<Return>)],
+    ) {
+        self.visit_signature(params, returns);
+        for (params, returns) in overloads.iter() {
+            self.visit_signature(params, returns);
+        }
+    }
+
+    /// Visit a class or module function. Defaults to [`visit_function`][TypeVisitor::visit_function]
+    fn visit_func(&mut self, func: &Func) {
+        self.visit_function(&func.params, &func.returns, &func.overloads)
+    }
+
+    /// Visit a single signature of a function, i.e. the main signature or one of its overloads
+    fn visit_signature(&mut self, params: &[Param], returns: &[Return]) {
+        params.iter().for_each(|v| v.ty.accept(self));
+        returns.iter().for_each(|v| v.ty.accept(self));
+    }
+}
+
+impl Type {
+    /// Call the [`TypeVisitor`] method that matches the type
+    pub fn accept<V: TypeVisitor + ?Sized>(&self, visitor: &mut V) {
+        match self {
+            Self::Single(name) => visitor.visit_single(name),
+            Self::Value(ty) => visitor.visit_value(ty),
+            Self::Alias(ty) => visitor.visit_alias(ty),
+            Self::Enum(name, types) => visitor.visit_enum(name, types),
+            Self::Class(class) => visitor.visit_class(class),
+            Self::Module(module) => visitor.visit_module(module),
+            Self::Tuple(types) => visitor.visit_tuple(types),
+            Self::Struct(entries) => visitor.visit_struct(entries),
+            Self::Variadic(ty) => visitor.visit_variadic(ty),
+            Self::Union(types) => visitor.visit_union(types),
+            Self::Intersection(types) => visitor.visit_intersection(types),
+            Self::Array(ty) => visitor.visit_array(ty),
+            Self::ArrayOfLen(ty, len) => visitor.visit_array_of_len(ty, *len),
+            Self::Map(key, value) => visitor.visit_map(key, value),
+            Self::SelfType => visitor.visit_self_type(),
+            Self::Function { params, returns, overloads, .. } => {
+                visitor.visit_function(params, returns, overloads)
+            }
+        }
+    }
+}